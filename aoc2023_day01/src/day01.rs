@@ -1,6 +1,8 @@
+use aoc2023_utils::Solution;
+
 pub fn get_calibration_value(s: &str) -> u32 {
-    let c1 = s.chars().find(|c| c.is_digit(10)).unwrap();
-    let c2 = s.chars().rfind(|c| c.is_digit(10)).unwrap();
+    let c1 = s.chars().find(|c| c.is_ascii_digit()).unwrap();
+    let c2 = s.chars().rfind(|c| c.is_ascii_digit()).unwrap();
     let d1 = c1.to_digit(10).unwrap();
     let d2 = c2.to_digit(10).unwrap();
     d1 * 10 + d2
@@ -14,7 +16,7 @@ pub fn get_calibration_value2(s: &str) -> u32 {
     let numbers = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
     let mut pos1 = usize::MAX;
-    let mut val1 = 0 as u32;
+    let mut val1 = 0_u32;
     for (idx, num_str) in number_strs.iter().enumerate() {
         if let Some(pos) = s.find(num_str) {
             if pos < pos1 {
@@ -25,7 +27,7 @@ pub fn get_calibration_value2(s: &str) -> u32 {
     }
 
     let mut pos2 = usize::MIN;
-    let mut val2 = 0 as u32;
+    let mut val2 = 0_u32;
     for (idx, num_str) in number_strs.iter().enumerate() {
         if let Some(pos) = s.rfind(num_str) {
             if pos >= pos2 {
@@ -39,13 +41,35 @@ pub fn get_calibration_value2(s: &str) -> u32 {
 }
 
 pub fn sum_calibration_values(s: &str) -> u32 {
-    s.lines().map(|line| get_calibration_value(line)).sum()
+    s.lines().map(get_calibration_value).sum()
 }
 
 pub fn sum_calibration_values2(s: &str) -> u32 {
-    s.lines().map(|line| get_calibration_value2(line)).sum()
+    s.lines().map(get_calibration_value2).sum()
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        input.to_string()
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_calibration_values(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_calibration_values2(parsed)
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day01);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,62 @@
+use aoc2023_utils::Rng;
+
+// Number of lines.
+pub const DEFAULT_SIZE: usize = 1000;
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+fn gen_piece(rng: &mut Rng) -> String {
+    match rng.range(0..3) {
+        0 => rng.range(1..10).to_string(),
+        1 => rng.choose(&DIGIT_WORDS).to_string(),
+        _ => (0..rng.range(1..6))
+            .map(|_| *rng.choose(LETTERS) as char)
+            .collect(),
+    }
+}
+
+fn gen_line(rng: &mut Rng) -> String {
+    let mut pieces = (0..rng.range(1..6))
+        .map(|_| gen_piece(rng))
+        .collect::<Vec<String>>();
+    // Part 1 needs at least one real digit on every line.
+    let digit_idx = rng.range(0..pieces.len() + 1);
+    pieces.insert(digit_idx, rng.range(1..10).to_string());
+    pieces.concat()
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size).map(|_| gen_line(&mut rng) + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_calibration_value, get_calibration_value2};
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let input = generate(3, 50);
+        assert_eq!(input.lines().count(), 50);
+        assert!(input
+            .lines()
+            .all(|line| line.chars().any(|c| c.is_ascii_digit())));
+
+        // Digits are never 0, and without spelled out digits part 2 reads a
+        // line the same as part 1.
+        for line in input.lines() {
+            let value = get_calibration_value(line);
+            assert!((11..=99).contains(&value) && value % 10 != 0, "{line}");
+            if !DIGIT_WORDS.iter().any(|word| line.contains(word)) {
+                assert_eq!(get_calibration_value2(line), value, "{line}");
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod day01;
+pub mod generate;
+
+pub use day01::{
+    get_calibration_value, get_calibration_value2, sum_calibration_values, sum_calibration_values2,
+    Day01,
+};
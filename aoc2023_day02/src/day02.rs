@@ -1,17 +1,19 @@
+use aoc2023_utils::Solution;
+
 #[derive(Debug, PartialEq)]
-struct Handful {
-    num_red: u32,
-    num_green: u32,
-    num_blue: u32,
+pub struct Handful {
+    pub num_red: u32,
+    pub num_green: u32,
+    pub num_blue: u32,
 }
 
 #[derive(Debug, PartialEq)]
-struct Game {
-    id: u32,
-    handfuls: Vec<Handful>,
+pub struct Game {
+    pub id: u32,
+    pub handfuls: Vec<Handful>,
 }
 
-fn parse_handful(s: &str) -> Handful {
+pub fn parse_handful(s: &str) -> Handful {
     let mut handful = Handful {
         num_red: 0,
         num_green: 0,
@@ -31,26 +33,22 @@ fn parse_handful(s: &str) -> Handful {
     handful
 }
 
-fn parse_line(line: &str) -> Game {
+pub fn parse_line(line: &str) -> Game {
     const ID_START_IDX: usize = 5;
     let colon_idx = line.find(':').unwrap();
     let id_str = &line[ID_START_IDX..colon_idx];
-    let id = u32::from_str_radix(id_str, 10).unwrap();
+    let id = id_str.parse().unwrap();
 
     let handfuls_str = &line[(colon_idx + 2)..];
     let handfuls = handfuls_str
         .split("; ")
-        .into_iter()
-        .map(|handful_str| parse_handful(handful_str))
+        .map(parse_handful)
         .collect::<Vec<Handful>>();
 
-    Game {
-        id: id,
-        handfuls: handfuls,
-    }
+    Game { id, handfuls }
 }
 
-fn get_max_handful_game(game: &Game) -> Game {
+pub fn get_max_handful_game(game: &Game) -> Game {
     let mut max_handful = Handful {
         num_red: 0,
         num_green: 0,
@@ -69,10 +67,14 @@ fn get_max_handful_game(game: &Game) -> Game {
     }
 }
 
-pub fn get_sum_of_possible_game_ids(s: &str, red: u32, green: u32, blue: u32) -> u32 {
-    s.lines()
-        .map(|line| parse_line(line))
-        .map(|game| get_max_handful_game(&game))
+pub fn parse_input(s: &str) -> Vec<Game> {
+    s.lines().map(parse_line).collect()
+}
+
+pub fn sum_of_possible_game_ids(games: &[Game], red: u32, green: u32, blue: u32) -> u32 {
+    games
+        .iter()
+        .map(get_max_handful_game)
         .filter(|game| {
             (game.handfuls[0].num_red <= red)
                 && (game.handfuls[0].num_green <= green)
@@ -82,16 +84,50 @@ pub fn get_sum_of_possible_game_ids(s: &str, red: u32, green: u32, blue: u32) ->
         .sum()
 }
 
-pub fn get_sum_of_power_of_min_possible_sets(s: &str) -> u32 {
-    s.lines()
-        .map(|line| parse_line(line))
-        .map(|game| get_max_handful_game(&game))
+pub fn get_sum_of_possible_game_ids(s: &str, red: u32, green: u32, blue: u32) -> u32 {
+    sum_of_possible_game_ids(&parse_input(s), red, green, blue)
+}
+
+pub fn sum_of_power_of_min_possible_sets(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(get_max_handful_game)
         .map(|game| {
             game.handfuls[0].num_red * game.handfuls[0].num_green * game.handfuls[0].num_blue
         })
         .sum()
 }
 
+pub fn get_sum_of_power_of_min_possible_sets(s: &str) -> u32 {
+    sum_of_power_of_min_possible_sets(&parse_input(s))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_of_possible_game_ids(parsed, 12, 13, 14)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_of_power_of_min_possible_sets(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|game| format!("{game:#?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day02);
+
 #[cfg(test)]
 mod tests {
     use super::*;
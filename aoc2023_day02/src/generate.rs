@@ -0,0 +1,73 @@
+use aoc2023_utils::Rng;
+
+// Number of games.
+pub const DEFAULT_SIZE: usize = 100;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+const MAX_CUBES: usize = 20;
+
+fn gen_handful(rng: &mut Rng) -> String {
+    let mut colors = COLORS.to_vec();
+    rng.shuffle(&mut colors);
+    colors.truncate(rng.range(1..4));
+    colors
+        .iter()
+        .map(|color| format!("{} {color}", rng.range(1..MAX_CUBES + 1)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (1..=size)
+        .map(|id| {
+            let handfuls = (0..rng.range(1..7))
+                .map(|_| gen_handful(&mut rng))
+                .collect::<Vec<String>>();
+            format!("Game {id}: {}\n", handfuls.join("; "))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let input = generate(3, 50);
+        assert_eq!(input.lines().count(), 50);
+
+        // Reads the most of each colour straight off the text, for part 1's
+        // limits of 12 red, 13 green and 14 blue.
+        let mut possible_ids = 0;
+        for (idx, line) in input.lines().enumerate() {
+            let (game, handfuls) = line.split_once(": ").unwrap();
+            assert_eq!(game, format!("Game {}", idx + 1));
+            let mut most = [0; 3];
+            for handful in handfuls.split("; ") {
+                let mut seen = [false; 3];
+                for cubes in handful.split(", ") {
+                    let (num, color) = cubes.split_once(' ').unwrap();
+                    let num = num.parse::<usize>().unwrap();
+                    let color_idx = COLORS.iter().position(|c| *c == color).unwrap();
+                    assert!((1..=MAX_CUBES).contains(&num));
+                    assert!(!seen[color_idx], "{handful}");
+                    seen[color_idx] = true;
+                    most[color_idx] = most[color_idx].max(num);
+                }
+            }
+            if most[0] <= 12 && most[1] <= 13 && most[2] <= 14 {
+                possible_ids += idx + 1;
+            }
+        }
+
+        let parsed = Day02.parse(&input);
+        assert_eq!(Day02.part1(&parsed) as usize, possible_ids);
+    }
+}
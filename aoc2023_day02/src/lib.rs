@@ -0,0 +1,8 @@
+mod day02;
+pub mod generate;
+
+pub use day02::{
+    get_max_handful_game, get_sum_of_possible_game_ids, get_sum_of_power_of_min_possible_sets,
+    parse_handful, parse_input, parse_line, sum_of_possible_game_ids,
+    sum_of_power_of_min_possible_sets, Day02, Game, Handful,
+};
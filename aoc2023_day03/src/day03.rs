@@ -1,30 +1,33 @@
+use aoc2023_utils::Solution;
 use std::collections::HashMap;
 
+pub type IdAndAdjacentCells = (u32, Vec<(usize, usize)>);
+
+#[derive(Debug, PartialEq)]
+pub struct Schematic {
+    pub symbol_locs: Vec<Vec<u8>>,
+    pub ids_and_coords: Vec<IdAndAdjacentCells>,
+}
+
 fn parse_schematic_for_symbols(s: &str) -> Vec<Vec<u8>> {
     s.lines()
         .map(|line| {
             line.chars()
-                .map(|c| {
-                    if char::is_numeric(c) {
-                        '.' as u8
-                    } else {
-                        c as u8
-                    }
-                })
+                .map(|c| if char::is_numeric(c) { b'.' } else { c as u8 })
                 .collect()
         })
         .collect()
 }
 
 fn get_ids_and_adjacent_cells(
-    s: &Vec<u8>,
+    s: &[u8],
     row_num: usize,
     max_rows: usize,
-) -> Vec<(u32, Vec<(usize, usize)>)> {
-    let mut ids = Vec::<(u32, Vec<(usize, usize)>)>::new();
+) -> Vec<IdAndAdjacentCells> {
+    let mut ids = Vec::<IdAndAdjacentCells>::new();
 
-    let mut start_idx = 0 as usize;
-    let mut end_idx = 0 as usize;
+    let mut start_idx = 0_usize;
+    let mut end_idx = 0_usize;
     while (start_idx < s.len()) && (end_idx < s.len()) {
         if !char::is_numeric(s[start_idx] as char) {
             start_idx += 1;
@@ -37,7 +40,7 @@ fn get_ids_and_adjacent_cells(
         }
 
         let id_str = std::str::from_utf8(&s[start_idx..end_idx]).unwrap();
-        let id = u32::from_str_radix(&id_str, 10).unwrap();
+        let id = id_str.parse().unwrap();
 
         let mut adjacencies: Vec<(usize, usize)> = vec![];
 
@@ -80,7 +83,7 @@ fn get_ids_and_adjacent_cells(
     ids
 }
 
-pub fn get_sum_of_ids(s: &str) -> u32 {
+pub fn parse_input(s: &str) -> Schematic {
     let lines = s
         .lines()
         .map(|line| line.as_bytes().to_vec())
@@ -88,7 +91,7 @@ pub fn get_sum_of_ids(s: &str) -> u32 {
 
     let symbol_locs = parse_schematic_for_symbols(s);
 
-    let mut ids_and_coords = Vec::<(u32, Vec<(usize, usize)>)>::new();
+    let mut ids_and_coords = Vec::<IdAndAdjacentCells>::new();
 
     lines
         .iter()
@@ -98,42 +101,37 @@ pub fn get_sum_of_ids(s: &str) -> u32 {
             ids_and_coords.extend(id_map);
         });
 
-    ids_and_coords
+    Schematic {
+        symbol_locs,
+        ids_and_coords,
+    }
+}
+
+pub fn sum_of_ids(schematic: &Schematic) -> u32 {
+    let symbol_locs = &schematic.symbol_locs;
+
+    schematic
+        .ids_and_coords
         .iter()
         .filter(|(_, coords)| {
             coords
                 .iter()
-                .filter(|(row, col)| symbol_locs[*row][*col] != ('.' as u8))
-                .next()
-                .is_some()
+                .any(|(row, col)| symbol_locs[*row][*col] != b'.')
         })
         .map(|(id, _)| *id)
         .sum()
 }
 
-pub fn get_sum_of_gear_ratios(s: &str) -> u32 {
-    let lines = s
-        .lines()
-        .map(|line| line.as_bytes().to_vec())
-        .collect::<Vec<Vec<u8>>>();
-
-    let symbol_locs = parse_schematic_for_symbols(s);
-
-    let mut ids_and_coords = Vec::<(u32, Vec<(usize, usize)>)>::new();
-
-    lines
-        .iter()
-        .enumerate()
-        .map(|(idx, line)| get_ids_and_adjacent_cells(line, idx, lines.len()))
-        .for_each(|id_map| {
-            ids_and_coords.extend(id_map);
-        });
+pub fn get_sum_of_ids(s: &str) -> u32 {
+    sum_of_ids(&parse_input(s))
+}
 
+pub fn sum_of_gear_ratios(schematic: &Schematic) -> u32 {
     let mut possible_gears = HashMap::<(usize, usize), Vec<u32>>::new();
 
-    for (id, coords) in &ids_and_coords {
+    for (id, coords) in &schematic.ids_and_coords {
         for (row, col) in coords {
-            if symbol_locs[*row][*col] == ('*' as u8) {
+            if schematic.symbol_locs[*row][*col] == b'*' {
                 let entry = possible_gears.entry((*row, *col)).or_default();
                 entry.push(*id);
             }
@@ -147,6 +145,32 @@ pub fn get_sum_of_gear_ratios(s: &str) -> u32 {
         .sum()
 }
 
+pub fn get_sum_of_gear_ratios(s: &str) -> u32 {
+    sum_of_gear_ratios(&parse_input(s))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Schematic;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_of_ids(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_of_gear_ratios(parsed)
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day03);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,64 +212,67 @@ mod tests {
             .map(|line| line.as_bytes().to_vec())
             .collect::<Vec<Vec<u8>>>();
 
-        let mut output = Vec::<(u32, Vec<(usize, usize)>)>::new();
-        output.push((467, vec![(0, 3), (1, 0), (1, 1), (1, 2), (1, 3)]));
-        output.push((
-            114,
-            vec![(0, 4), (0, 8), (1, 4), (1, 5), (1, 6), (1, 7), (1, 8)],
-        ));
+        let output = vec![
+            (467, vec![(0, 3), (1, 0), (1, 1), (1, 2), (1, 3)]),
+            (
+                114,
+                vec![(0, 4), (0, 8), (1, 4), (1, 5), (1, 6), (1, 7), (1, 8)],
+            ),
+        ];
         assert_eq!(
             get_ids_and_adjacent_cells(&lines[0], 0, lines.len()),
             output
         );
 
-        let mut output = Vec::<(u32, Vec<(usize, usize)>)>::new();
-        output.push((
-            35,
-            vec![
-                (1, 1),
-                (1, 2),
-                (1, 3),
-                (1, 4),
-                (2, 1),
-                (2, 4),
-                (3, 1),
-                (3, 2),
-                (3, 3),
-                (3, 4),
-            ],
-        ));
-        output.push((
-            633,
-            vec![
-                (1, 5),
-                (1, 6),
-                (1, 7),
-                (1, 8),
-                (1, 9),
-                (2, 5),
-                (2, 9),
-                (3, 5),
-                (3, 6),
-                (3, 7),
-                (3, 8),
-                (3, 9),
-            ],
-        ));
+        let output = vec![
+            (
+                35,
+                vec![
+                    (1, 1),
+                    (1, 2),
+                    (1, 3),
+                    (1, 4),
+                    (2, 1),
+                    (2, 4),
+                    (3, 1),
+                    (3, 2),
+                    (3, 3),
+                    (3, 4),
+                ],
+            ),
+            (
+                633,
+                vec![
+                    (1, 5),
+                    (1, 6),
+                    (1, 7),
+                    (1, 8),
+                    (1, 9),
+                    (2, 5),
+                    (2, 9),
+                    (3, 5),
+                    (3, 6),
+                    (3, 7),
+                    (3, 8),
+                    (3, 9),
+                ],
+            ),
+        ];
         assert_eq!(
             get_ids_and_adjacent_cells(&lines[2], 2, lines.len()),
             output
         );
 
-        let mut output = Vec::<(u32, Vec<(usize, usize)>)>::new();
-        output.push((
-            664,
-            vec![(8, 0), (8, 1), (8, 2), (8, 3), (8, 4), (9, 0), (9, 4)],
-        ));
-        output.push((
-            598,
-            vec![(8, 4), (8, 5), (8, 6), (8, 7), (8, 8), (9, 4), (9, 8)],
-        ));
+        let output = vec![
+            (
+                664,
+                vec![(8, 0), (8, 1), (8, 2), (8, 3), (8, 4), (9, 0), (9, 4)],
+            ),
+            (
+                598,
+                vec![(8, 4), (8, 5), (8, 6), (8, 7), (8, 8), (9, 4), (9, 8)],
+            ),
+        ];
         assert_eq!(
             get_ids_and_adjacent_cells(&lines[9], 9, lines.len()),
             output
@@ -1,7 +0,0 @@
-pub mod day03;
-
-fn main() {
-    let input = aoc2023_utils::get_input("inputs/day03.txt");
-    let result = day03::get_sum_of_ids(&input);
-    println!("{}", result);
-}
@@ -0,0 +1,71 @@
+use aoc2023_utils::Rng;
+
+// Width and height of the schematic.
+pub const DEFAULT_SIZE: usize = 140;
+
+const SYMBOLS: &[u8] = b"*#+$/@%=&-";
+
+fn gen_row(rng: &mut Rng, width: usize) -> Vec<u8> {
+    let mut row = vec![b'.'; width];
+    let mut col = rng.range(0..4);
+    while col < width {
+        let num_digits = rng.range(1..4).min(width - col);
+        let num = rng.range(10_usize.pow(num_digits as u32 - 1)..10_usize.pow(num_digits as u32));
+        row[col..col + num_digits].copy_from_slice(num.to_string().as_bytes());
+        // Always leave a gap so neighbouring numbers don't run together.
+        col += num_digits + rng.range(1..8);
+    }
+    row
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut rows = (0..size)
+        .map(|_| gen_row(&mut rng, size))
+        .collect::<Vec<Vec<u8>>>();
+
+    // Gears are the interesting symbol, so make them about a third of them.
+    for cell in rows.iter_mut().flatten() {
+        if *cell == b'.' && rng.chance(0.08) {
+            *cell = if rng.chance(0.35) {
+                b'*'
+            } else {
+                *rng.choose(SYMBOLS)
+            };
+        }
+    }
+
+    rows.iter()
+        .map(|row| String::from_utf8(row.clone()).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 30), generate(1, 30));
+        assert_ne!(generate(1, 30), generate(2, 30));
+
+        let input = generate(3, 30);
+        assert_eq!(input.lines().count(), 30);
+        assert!(input.lines().all(|line| line.len() == 30));
+        assert!(input.contains('*'));
+
+        // Numbers are one to three digits without leading zeros, however
+        // close they're packed.
+        for line in input.lines() {
+            for num in line.split(|c: char| !c.is_ascii_digit()) {
+                assert!(num.len() <= 3 && !num.starts_with('0'), "{line}");
+            }
+        }
+
+        let parsed = Day03.parse(&input);
+        assert!(Day03.part1(&parsed) > 0);
+        assert!(Day03.part2(&parsed) > 0);
+    }
+}
@@ -0,0 +1,7 @@
+mod day03;
+pub mod generate;
+
+pub use day03::{
+    get_sum_of_gear_ratios, get_sum_of_ids, parse_input, sum_of_gear_ratios, sum_of_ids, Day03,
+    IdAndAdjacentCells, Schematic,
+};
@@ -1,11 +1,13 @@
+use aoc2023_utils::Solution;
+
 #[derive(Debug, PartialEq)]
-struct Card {
-    id: u32,
-    winners: Vec<u32>,
-    numbers: Vec<u32>,
+pub struct Card {
+    pub id: u32,
+    pub winners: Vec<u32>,
+    pub numbers: Vec<u32>,
 }
 
-fn parse_card(s: &str) -> Card {
+pub fn parse_card(s: &str) -> Card {
     let colon_idx = s.find(':').unwrap();
     let pipe_idx = s.find('|').unwrap();
     let id = s[5..colon_idx].trim().parse::<u32>().unwrap();
@@ -25,20 +27,20 @@ fn parse_card(s: &str) -> Card {
         .collect::<Vec<u32>>();
 
     Card {
-        id: id,
-        winners: winners,
-        numbers: numbers,
+        id,
+        winners,
+        numbers,
     }
 }
 
-fn get_num_matches(card: &Card) -> u32 {
+pub fn get_num_matches(card: &Card) -> u32 {
     let mut winners = card.winners.clone();
     let mut numbers = card.numbers.clone();
     winners.sort();
     numbers.sort();
 
-    let mut idx1 = 0 as usize;
-    let mut idx2 = 0 as usize;
+    let mut idx1 = 0_usize;
+    let mut idx2 = 0_usize;
     let mut matches = 0;
 
     let max_idx1 = winners.len() - 1;
@@ -65,13 +67,17 @@ fn get_num_matches(card: &Card) -> u32 {
     matches
 }
 
-pub fn get_points_for_cards(s: &str) -> u32 {
-    s.lines()
-        .map(|line| parse_card(line))
-        .map(|card| get_num_matches(&card))
+pub fn parse_input(s: &str) -> Vec<Card> {
+    s.lines().map(parse_card).collect()
+}
+
+pub fn points_for_cards(cards: &[Card]) -> u32 {
+    cards
+        .iter()
+        .map(get_num_matches)
         .map(|num_matches| {
             if num_matches > 0 {
-                (2 as u32).pow(num_matches - 1)
+                2_u32.pow(num_matches - 1)
             } else {
                 0
             }
@@ -79,16 +85,16 @@ pub fn get_points_for_cards(s: &str) -> u32 {
         .sum()
 }
 
-pub fn get_num_cards_after_rewinning(s: &str) -> u32 {
-    let cards = s
-        .lines()
-        .map(|line| parse_card(line))
-        .collect::<Vec<Card>>();
+pub fn get_points_for_cards(s: &str) -> u32 {
+    points_for_cards(&parse_input(s))
+}
+
+pub fn num_cards_after_rewinning(cards: &[Card]) -> u32 {
     let mut card_counts = vec![1; cards.len() + 1];
     card_counts[0] = 0;
 
     for card in cards {
-        let num_matches = get_num_matches(&card);
+        let num_matches = get_num_matches(card);
         let num_of_current_card = card_counts[card.id as usize];
         for won_card_id in (card.id + 1)..=(card.id + num_matches) {
             card_counts[won_card_id as usize] += num_of_current_card;
@@ -98,6 +104,36 @@ pub fn get_num_cards_after_rewinning(s: &str) -> u32 {
     card_counts.iter().sum()
 }
 
+pub fn get_num_cards_after_rewinning(s: &str) -> u32 {
+    num_cards_after_rewinning(&parse_input(s))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Vec<Card>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        points_for_cards(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        num_cards_after_rewinning(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|card| format!("{card:#?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day04);
+
 #[cfg(test)]
 mod tests {
     use super::*;
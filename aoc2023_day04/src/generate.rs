@@ -0,0 +1,101 @@
+use aoc2023_utils::Rng;
+
+// Number of cards.
+pub const DEFAULT_SIZE: usize = 200;
+
+const NUM_WINNERS: usize = 10;
+const NUM_NUMBERS: usize = 25;
+const MAX_NUMBER: usize = 99;
+// Part 2 counts card copies in a u32, and copies grow geometrically. Matches
+// are cut back as needed to keep the total under this.
+const MAX_TOTAL_CARDS: u64 = u32::MAX as u64 / 2;
+
+fn fmt_nums(nums: &[usize]) -> String {
+    nums.iter()
+        .map(|num| format!("{num:>2}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn gen_card(rng: &mut Rng, id: usize, num_matches: usize) -> String {
+    let mut pool = (1..=MAX_NUMBER).collect::<Vec<usize>>();
+    rng.shuffle(&mut pool);
+
+    let winners = &pool[..NUM_WINNERS];
+    let mut numbers = winners[..num_matches].to_vec();
+    numbers.extend(&pool[NUM_WINNERS..NUM_WINNERS + NUM_NUMBERS - num_matches]);
+    rng.shuffle(&mut numbers);
+
+    format!(
+        "Card {id:>3}: {} | {}",
+        fmt_nums(winners),
+        fmt_nums(&numbers)
+    )
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let mut copies = vec![1_u64; size];
+    let mut total = size as u64;
+    let mut cards = String::new();
+    for idx in 0..size {
+        let mut num_matches = if rng.chance(0.4) {
+            0
+        } else {
+            rng.range(1..NUM_WINNERS + 1)
+        };
+        // Can't win copies of cards past the end of the table.
+        num_matches = num_matches.min(size - idx - 1);
+        let num_copies = copies[idx];
+        num_matches = num_matches.min(((MAX_TOTAL_CARDS - total) / num_copies) as usize);
+
+        for won in &mut copies[idx + 1..=idx + num_matches] {
+            *won += num_copies;
+        }
+        total += num_copies * num_matches as u64;
+
+        cards += &gen_card(&mut rng, idx + 1, num_matches);
+        cards += "\n";
+    }
+
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let input = generate(3, 50);
+        assert_eq!(input.lines().count(), 50);
+
+        // No card wins copies of cards past the end of the table.
+        let parsed = Day04.parse(&input);
+        for (idx, card) in parsed.iter().enumerate() {
+            assert_eq!(card.id as usize, idx + 1);
+            assert_eq!(card.winners.len(), NUM_WINNERS);
+            assert_eq!(card.numbers.len(), NUM_NUMBERS);
+            let num_matches = card
+                .numbers
+                .iter()
+                .filter(|num| card.winners.contains(num))
+                .count();
+            assert!(num_matches < parsed.len() - idx);
+        }
+        assert!(Day04.part1(&parsed) > 0);
+        assert!(Day04.part2(&parsed) > 50);
+    }
+
+    #[test]
+    fn test_generate_keeps_copies_in_range() {
+        let parsed = Day04.parse(&generate(4, 2000));
+        assert!(Day04.part2(&parsed) as u64 <= MAX_TOTAL_CARDS);
+    }
+}
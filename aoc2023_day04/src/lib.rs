@@ -0,0 +1,7 @@
+mod day04;
+pub mod generate;
+
+pub use day04::{
+    get_num_cards_after_rewinning, get_num_matches, get_points_for_cards,
+    num_cards_after_rewinning, parse_card, parse_input, points_for_cards, Card, Day04,
+};
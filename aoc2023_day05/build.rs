@@ -0,0 +1,3 @@
+fn main() {
+    aoc2023_utils::fixtures::generate_tests();
+}
@@ -1,7 +1,8 @@
+use aoc2023_utils::{cancel, log_info, Solution};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Category {
+pub enum Category {
     Seed,
     Soil,
     Fertilizer,
@@ -13,7 +14,7 @@ enum Category {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct ParseCategoryError;
+pub struct ParseCategoryError;
 
 impl FromStr for Category {
     type Err = ParseCategoryError;
@@ -34,10 +35,10 @@ impl FromStr for Category {
 }
 
 #[derive(Debug, PartialEq)]
-struct MapRange {
-    from: u32,
-    to: u32,
-    len: u32,
+pub struct MapRange {
+    pub from: u32,
+    pub to: u32,
+    pub len: u32,
 }
 
 impl MapRange {
@@ -51,10 +52,10 @@ impl MapRange {
 }
 
 #[derive(Debug, PartialEq)]
-struct Map {
-    cat_from: Category,
-    cat_to: Category,
-    ranges: Vec<MapRange>,
+pub struct Map {
+    pub cat_from: Category,
+    pub cat_to: Category,
+    pub ranges: Vec<MapRange>,
 }
 
 impl Map {
@@ -97,13 +98,13 @@ fn parse_category_map(label_line: &str, range_lines: Vec<&str>) -> Map {
     map_ranges.sort_by(|a, b| a.from.partial_cmp(&b.from).unwrap());
 
     Map {
-        cat_from: cat_from,
-        cat_to: cat_to,
+        cat_from,
+        cat_to,
         ranges: map_ranges,
     }
 }
 
-fn parse_input(s: &str) -> (Vec<u32>, Vec<Map>) {
+pub fn parse_input(s: &str) -> (Vec<u32>, Vec<Map>) {
     let mut category_maps: Vec<Map> = vec![];
 
     let mut lines = s.lines();
@@ -141,7 +142,7 @@ fn parse_input(s: &str) -> (Vec<u32>, Vec<Map>) {
     (seeds, category_maps)
 }
 
-fn get_mapped_val(map: &Map, key: u32) -> u32 {
+pub fn get_mapped_val(map: &Map, key: u32) -> u32 {
     for range in &map.ranges {
         if key < range.from {
             return key;
@@ -156,15 +157,15 @@ fn get_mapped_val(map: &Map, key: u32) -> u32 {
     key
 }
 
-fn get_loc_for_seed(seed: u32, cat_maps: &Vec<Map>) -> u32 {
+pub fn get_loc_for_seed(seed: u32, cat_maps: &[Map]) -> u32 {
     let mut key = seed;
     for map in cat_maps {
-        key = get_mapped_val(&map, key);
+        key = get_mapped_val(map, key);
     }
     key
 }
 
-fn is_seed_in_range(seed: u32, ranges: &Vec<SeedRange>) -> bool {
+fn is_seed_in_range(seed: u32, ranges: &[SeedRange]) -> bool {
     for range in ranges {
         if seed < range.start {
             return false;
@@ -179,19 +180,20 @@ fn is_seed_in_range(seed: u32, ranges: &Vec<SeedRange>) -> bool {
     false
 }
 
-pub fn get_lowest_loc_for_seed(s: &str) -> u32 {
-    let (seeds, cat_maps) = parse_input(s);
-
+pub fn lowest_loc_for_seed(seeds: &[u32], cat_maps: &[Map]) -> u32 {
     seeds
         .iter()
-        .map(|seed| get_loc_for_seed(*seed, &cat_maps))
+        .map(|seed| get_loc_for_seed(*seed, cat_maps))
         .min()
         .unwrap()
 }
 
-pub fn get_lowest_loc_for_seed_ranges(s: &str) -> u32 {
+pub fn get_lowest_loc_for_seed(s: &str) -> u32 {
     let (seeds, cat_maps) = parse_input(s);
+    lowest_loc_for_seed(&seeds, &cat_maps)
+}
 
+pub fn lowest_loc_for_seed_ranges(seeds: &[u32], cat_maps: &[Map]) -> u32 {
     let mut seed_ranges: Vec<SeedRange> = vec![];
     let mut seeds_iter = seeds.iter();
     loop {
@@ -211,13 +213,14 @@ pub fn get_lowest_loc_for_seed_ranges(s: &str) -> u32 {
         .collect::<Vec<Map>>();
     cat_maps.reverse();
 
-    let mut loc = 0 as u32;
+    let mut loc = 0_u32;
     loop {
+        cancel::check();
         // we reversed everything, so this should actually be getting the seed
         // from the location
         let seed = get_loc_for_seed(loc, &cat_maps);
         if loc % 1000000 == 0 {
-            println!("From loc {loc}, got seed {seed}");
+            log_info!("From loc {loc}, got seed {seed}");
         }
         if is_seed_in_range(seed, &seed_ranges) {
             return loc;
@@ -227,9 +230,41 @@ pub fn get_lowest_loc_for_seed_ranges(s: &str) -> u32 {
     }
 }
 
+pub fn get_lowest_loc_for_seed_ranges(s: &str) -> u32 {
+    let (seeds, cat_maps) = parse_input(s);
+    lowest_loc_for_seed_ranges(&seeds, &cat_maps)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = (Vec<u32>, Vec<Map>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        lowest_loc_for_seed(&parsed.0, &parsed.1)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        lowest_loc_for_seed_ranges(&parsed.0, &parsed.1)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.1.get(idx).map(|map| format!("{map:#?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day05);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc2023_utils::diff::{self, Differ};
 
     const SAMPLE_INPUT: &str = concat!(
         "seeds: 79 14 55 13\n",
@@ -360,4 +395,77 @@ mod tests {
     fn test_get_lowest_loc_for_seed_ranges() {
         assert_eq!(get_lowest_loc_for_seed_ranges(SAMPLE_INPUT), 46);
     }
+
+    // Maps every seed in every range forward instead of searching backwards
+    // from the locations. Only practical on small almanacs.
+    fn lowest_loc_for_seed_ranges_forward(seeds: &[u32], cat_maps: &[Map]) -> u32 {
+        seeds
+            .chunks(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .map(|seed| get_loc_for_seed(seed, cat_maps))
+            .min()
+            .unwrap()
+    }
+
+    // Almanacs with a seed range dropped or halved, or a whole map dropped.
+    // Anything else could stop the maps from being one-to-one, which the
+    // backwards search relies on.
+    fn shrink_almanac(input: &str) -> Vec<String> {
+        let mut blocks = input.trim_end().split("\n\n").collect::<Vec<&str>>();
+        let seeds = parse_input(blocks.remove(0)).0;
+        let seed_ranges = seeds
+            .chunks(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<(u32, u32)>>();
+
+        let mut shrunk_seed_ranges = vec![];
+        if seed_ranges.len() > 1 {
+            shrunk_seed_ranges.extend(diff::without_each(&seed_ranges));
+        }
+        for idx in 0..seed_ranges.len() {
+            if seed_ranges[idx].1 > 1 {
+                let mut seed_ranges = seed_ranges.clone();
+                seed_ranges[idx].1 /= 2;
+                shrunk_seed_ranges.push(seed_ranges);
+            }
+        }
+
+        let to_almanac = |seed_ranges: &[(u32, u32)], blocks: &[&str]| {
+            let seeds = seed_ranges
+                .iter()
+                .map(|(start, len)| format!("{start} {len}"))
+                .collect::<Vec<String>>();
+            let mut almanac = vec![format!("seeds: {}", seeds.join(" "))];
+            almanac.extend(blocks.iter().map(ToString::to_string));
+            almanac.join("\n\n") + "\n"
+        };
+        let mut shrunk = shrunk_seed_ranges
+            .iter()
+            .map(|seed_ranges| to_almanac(seed_ranges, &blocks))
+            .collect::<Vec<String>>();
+        if blocks.len() > 1 {
+            for blocks in diff::without_each(&blocks) {
+                shrunk.push(to_almanac(&seed_ranges, &blocks));
+            }
+        }
+
+        shrunk
+    }
+
+    #[test]
+    fn test_lowest_loc_for_seed_ranges_differential() {
+        Differ {
+            generate: |seed| crate::generate::generate(seed, 2),
+            shrink: |input| shrink_almanac(input),
+            reference: |input| {
+                let (seeds, cat_maps) = parse_input(input);
+                lowest_loc_for_seed_ranges_forward(&seeds, &cat_maps)
+            },
+            candidate: |input| {
+                let (seeds, cat_maps) = parse_input(input);
+                lowest_loc_for_seed_ranges(&seeds, &cat_maps)
+            },
+        }
+        .assert_agree(0..20);
+    }
 }
@@ -0,0 +1,126 @@
+use aoc2023_utils::Rng;
+
+// Number of ranges in each map. Every value stays below `size * SPAN_PER_RANGE`,
+// which also bounds how far part 2's search from location 0 has to go.
+pub const DEFAULT_SIZE: usize = 30;
+
+const SPAN_PER_RANGE: usize = 10_000;
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// `n` distinct sorted values in 1..span.
+fn gen_cuts(rng: &mut Rng, n: usize, span: usize) -> Vec<usize> {
+    let mut cuts = vec![];
+    while cuts.len() < n {
+        let cut = rng.range(1..span);
+        if !cuts.contains(&cut) {
+            cuts.push(cut);
+        }
+    }
+    cuts.sort();
+    cuts
+}
+
+// Splits 0..span into `num_ranges` pieces and lays them back out in a random
+// order, so the map is a permutation of 0..span like the real ones.
+fn gen_map(rng: &mut Rng, num_ranges: usize, span: usize) -> Vec<(usize, usize, usize)> {
+    let mut bounds = vec![0];
+    bounds.extend(gen_cuts(rng, num_ranges - 1, span));
+    bounds.push(span);
+    let mut pieces = bounds
+        .windows(2)
+        .map(|bound| (bound[0], bound[1] - bound[0]))
+        .collect::<Vec<(usize, usize)>>();
+    rng.shuffle(&mut pieces);
+
+    let mut dest = 0;
+    let mut ranges = pieces
+        .into_iter()
+        .map(|(src, len)| {
+            dest += len;
+            (dest - len, src, len)
+        })
+        .collect::<Vec<(usize, usize, usize)>>();
+    rng.shuffle(&mut ranges);
+    ranges
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let num_ranges = size.max(1);
+    let span = num_ranges * SPAN_PER_RANGE;
+
+    // Disjoint (start, len) pairs for part 2, which part 1 reads as seeds.
+    let num_seed_ranges = rng.range(2..11);
+    let cuts = gen_cuts(&mut rng, num_seed_ranges * 2, span);
+    let mut seed_ranges = cuts
+        .chunks(2)
+        .map(|pair| (pair[0], pair[1] - pair[0]))
+        .collect::<Vec<(usize, usize)>>();
+    rng.shuffle(&mut seed_ranges);
+    let seeds = seed_ranges
+        .iter()
+        .map(|(start, len)| format!("{start} {len}"))
+        .collect::<Vec<String>>();
+
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+    for cats in CATEGORIES.windows(2) {
+        almanac += &format!("\n{}-to-{} map:\n", cats[0], cats[1]);
+        for (dest, src, len) in gen_map(&mut rng, num_ranges, span) {
+            almanac += &format!("{dest} {src} {len}\n");
+        }
+    }
+
+    almanac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 5), generate(1, 5));
+        assert_ne!(generate(1, 5), generate(2, 5));
+
+        let parsed = Day05.parse(&generate(3, 5));
+        assert_eq!(parsed.1.len(), 7);
+        assert!(parsed.1.iter().all(|map| map.ranges.len() == 5));
+        // Seeds and maps all stay inside the same span, so both parts do too.
+        assert!(parsed
+            .0
+            .iter()
+            .all(|seed| (*seed as usize) < 5 * SPAN_PER_RANGE));
+        assert!((Day05.part1(&parsed) as usize) < 5 * SPAN_PER_RANGE);
+        assert!((Day05.part2(&parsed) as usize) < 5 * SPAN_PER_RANGE);
+    }
+
+    #[test]
+    fn test_gen_map_is_a_permutation() {
+        let mut rng = Rng::new(4);
+        let map = gen_map(&mut rng, 6, 100);
+
+        let mut srcs = map
+            .iter()
+            .flat_map(|(_, src, len)| *src..src + len)
+            .collect::<Vec<usize>>();
+        let mut dests = map
+            .iter()
+            .flat_map(|(dest, _, len)| *dest..dest + len)
+            .collect::<Vec<usize>>();
+        srcs.sort();
+        dests.sort();
+        assert_eq!(srcs, (0..100).collect::<Vec<usize>>());
+        assert_eq!(dests, (0..100).collect::<Vec<usize>>());
+    }
+}
@@ -0,0 +1,8 @@
+mod day05;
+pub mod generate;
+
+pub use day05::{
+    get_loc_for_seed, get_lowest_loc_for_seed, get_lowest_loc_for_seed_ranges, get_mapped_val,
+    lowest_loc_for_seed, lowest_loc_for_seed_ranges, parse_input, Category, Day05, Map, MapRange,
+    ParseCategoryError,
+};
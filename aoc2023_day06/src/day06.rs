@@ -1,10 +1,12 @@
+use aoc2023_utils::Solution;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Race {
-    time: u64,
-    dist: u64,
+pub struct Race {
+    pub time: u64,
+    pub dist: u64,
 }
 
-fn parse_input(s: &str) -> Vec<Race> {
+pub fn parse_input(s: &str) -> Vec<Race> {
     let mut lines = s.lines();
     let time_line = lines.next().unwrap();
     let dist_line = lines.next().unwrap();
@@ -49,8 +51,8 @@ fn parse_input_single(s: &str) -> Race {
     Race { time, dist }
 }
 
-fn get_num_wins(race: &Race) -> u32 {
-    let a = -1 as f64;
+pub fn get_num_wins(race: &Race) -> u32 {
+    let a = -1_f64;
     let b = race.time as f64;
     let c = -((race.dist + 1) as f64);
 
@@ -62,13 +64,16 @@ fn get_num_wins(race: &Race) -> u32 {
     ans2 - ans1 + 1
 }
 
+pub fn product_of_ways_to_win(races: &[Race]) -> u32 {
+    races.iter().map(get_num_wins).product()
+}
+
 pub fn get_product_of_ways_to_win(s: &str) -> u32 {
-    let races = parse_input(s);
-    races.iter().map(|race| get_num_wins(race)).product()
+    product_of_ways_to_win(&parse_input(s))
 }
 
 fn update_race_line(line: &str) -> String {
-    let digit_idx = line.find(|c: char| c.is_digit(10)).unwrap();
+    let digit_idx = line.find(|c: char| c.is_ascii_digit()).unwrap();
     let digits = line[digit_idx..]
         .chars()
         .filter(|c| *c != ' ')
@@ -86,14 +91,44 @@ fn update_race_input(s: &str) -> String {
     new_input
 }
 
-pub fn get_num_of_ways_to_win_single_race(s: &str) -> u32 {
+pub fn parse_input_single_race(s: &str) -> Race {
     let new_input = update_race_input(s);
 
-    let race = parse_input_single(&new_input);
+    parse_input_single(&new_input)
+}
+
+pub fn get_num_of_ways_to_win_single_race(s: &str) -> u32 {
+    get_num_wins(&parse_input_single_race(s))
+}
+
+pub struct Day06;
 
-    get_num_wins(&race)
+impl Solution for Day06 {
+    // The same sheet read as separate races and, ignoring the spaces, as a
+    // single race.
+    type Parsed = (Vec<Race>, Race);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        (parse_input(input), parse_input_single_race(input))
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        product_of_ways_to_win(&parsed.0)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        get_num_wins(&parsed.1)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.0.get(idx).map(|race| format!("{race:?}"))
+    }
 }
 
+aoc2023_utils::fixture_tests!(Day06);
+
 #[cfg(test)]
 mod tests {
     use super::*;
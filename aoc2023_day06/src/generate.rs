@@ -0,0 +1,70 @@
+use aoc2023_utils::Rng;
+
+// Number of races.
+pub const DEFAULT_SIZE: usize = 4;
+// Part 2 glues every race's digits together into one u64 race, which only fits
+// 4 races' worth. Bigger sizes get clamped to it.
+pub const MAX_SIZE: usize = 4;
+
+// The best distance for a race of `time`, from holding the button for half of
+// it.
+fn best_dist(time: u64) -> u64 {
+    (time / 2) * time.div_ceil(2)
+}
+
+fn fmt_line(label: &str, nums: &[u64]) -> String {
+    let nums = nums
+        .iter()
+        .map(|num| format!("{num:>5}"))
+        .collect::<String>();
+    format!("{label:<9}{nums}\n")
+}
+
+fn concat_digits(nums: &[u64]) -> u64 {
+    nums.iter()
+        .map(|num| num.to_string())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let num_races = size.clamp(1, MAX_SIZE);
+
+    // Every race must be winnable, and so must the combined race. Two-digit
+    // times make the combined record nearly always beatable, but check anyway.
+    loop {
+        let times = (0..num_races)
+            .map(|_| rng.range(10..100) as u64)
+            .collect::<Vec<u64>>();
+        let dists = times
+            .iter()
+            .map(|time| rng.below(best_dist(*time)))
+            .collect::<Vec<u64>>();
+
+        if concat_digits(&dists) < best_dist(concat_digits(&times)) {
+            return fmt_line("Time:", &times) + &fmt_line("Distance:", &dists);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day06;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 4), generate(1, 4));
+        assert_ne!(generate(1, 4), generate(2, 4));
+
+        for seed in 0..20 {
+            let parsed = Day06.parse(&generate(seed, 4));
+            assert_eq!(parsed.0.len(), 4);
+            assert!(Day06.part1(&parsed) > 0);
+            assert!(Day06.part2(&parsed) > 0);
+        }
+    }
+}
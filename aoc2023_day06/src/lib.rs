@@ -0,0 +1,7 @@
+mod day06;
+pub mod generate;
+
+pub use day06::{
+    get_num_of_ways_to_win_single_race, get_num_wins, get_product_of_ways_to_win, parse_input,
+    parse_input_single_race, product_of_ways_to_win, Day06, Race,
+};
@@ -1,10 +1,11 @@
+use aoc2023_utils::Solution;
 use std::cmp::Ordering;
 
 const CARD_TYPES: &str = "23456789TJQKA";
 const CARD_TYPES_J: &str = "J23456789TQKA";
 
 #[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
-enum HandKind {
+pub enum HandKind {
     FiveOfKind = 1,
     FourOfKind = 2,
     FullHouse = 3,
@@ -14,15 +15,27 @@ enum HandKind {
     HighCard = 7,
 }
 
+// `kind` comes from `cards`, so hands only come from parse_hand() and
+// use_joker().
 #[derive(Debug, PartialEq, /*Copy,*/ Clone)]
-struct Hand {
+pub struct Hand {
     cards: String,
     kind: HandKind,
 }
 
+impl Hand {
+    pub fn cards(&self) -> &str {
+        &self.cards
+    }
+
+    pub fn kind(&self) -> HandKind {
+        self.kind
+    }
+}
+
 fn do_cmp_hand(hand1: &Hand, hand2: &Hand, card_types: &str) -> Ordering {
     if hand1.kind != hand2.kind {
-        return hand1.kind.partial_cmp(&hand2.kind).unwrap();
+        hand1.kind.partial_cmp(&hand2.kind).unwrap()
     } else {
         let mut i = 0;
         while i < hand1.cards.len() {
@@ -37,32 +50,32 @@ fn do_cmp_hand(hand1: &Hand, hand2: &Hand, card_types: &str) -> Ordering {
             }
             i += 1;
         }
-        return Ordering::Equal;
+        Ordering::Equal
     }
 }
 
-fn cmp_hand(hand1: &Hand, hand2: &Hand) -> Ordering {
+pub fn cmp_hand(hand1: &Hand, hand2: &Hand) -> Ordering {
     do_cmp_hand(hand1, hand2, CARD_TYPES)
 }
 
-fn cmp_hand_j(hand1: &Hand, hand2: &Hand) -> Ordering {
+pub fn cmp_hand_j(hand1: &Hand, hand2: &Hand) -> Ordering {
     do_cmp_hand(hand1, hand2, CARD_TYPES_J)
 }
 
 #[derive(Debug, PartialEq, /*Copy,*/ Clone)]
-struct HandWithBid {
-    hand: Hand,
-    bid: u32,
+pub struct HandWithBid {
+    pub hand: Hand,
+    pub bid: u32,
 }
 
-fn parse_hand(hand_str: &str) -> Hand {
+pub fn parse_hand(hand_str: &str) -> Hand {
     let mut hand_chars = hand_str.chars().collect::<Vec<char>>();
     hand_chars.sort();
 
     let mut num_types = 1;
     let mut longest_type_run = 1;
     let mut current_type_run = 1;
-    let mut idx = 1 as usize;
+    let mut idx = 1_usize;
     while idx < hand_chars.len() {
         if hand_chars[idx - 1] != hand_chars[idx] {
             longest_type_run = longest_type_run.max(current_type_run);
@@ -101,11 +114,11 @@ fn parse_hand(hand_str: &str) -> Hand {
 
     Hand {
         cards: hand_str.to_string(),
-        kind: kind,
+        kind,
     }
 }
 
-fn parse_hand_with_bid(s: &str) -> HandWithBid {
+pub fn parse_hand_with_bid(s: &str) -> HandWithBid {
     let mut parts = s.split(' ');
     let hand_str = parts.next().unwrap();
     let bid_str = parts.next().unwrap();
@@ -116,14 +129,14 @@ fn parse_hand_with_bid(s: &str) -> HandWithBid {
     }
 }
 
-fn parse_all_hands(s: &str) -> Vec<HandWithBid> {
+pub fn parse_all_hands(s: &str) -> Vec<HandWithBid> {
     s.lines()
-        .map(|line| parse_hand_with_bid(line))
+        .map(parse_hand_with_bid)
         .collect::<Vec<HandWithBid>>()
 }
 
-fn calc_winnings(sorted_hands_with_bids: &Vec<HandWithBid>) -> u32 {
-    let mut winnings = 0 as u32;
+fn calc_winnings(sorted_hands_with_bids: &[HandWithBid]) -> u32 {
+    let mut winnings = 0_u32;
     sorted_hands_with_bids
         .iter()
         .enumerate()
@@ -134,13 +147,17 @@ fn calc_winnings(sorted_hands_with_bids: &Vec<HandWithBid>) -> u32 {
     winnings
 }
 
-pub fn get_winnings(s: &str) -> u32 {
-    let mut sorted_hands_with_bids = parse_all_hands(s);
+pub fn winnings(hands_with_bids: &[HandWithBid]) -> u32 {
+    let mut sorted_hands_with_bids = hands_with_bids.to_vec();
     sorted_hands_with_bids.sort_by(|a, b| cmp_hand(&a.hand, &b.hand).reverse());
     calc_winnings(&sorted_hands_with_bids)
 }
 
-fn use_joker(hand: &Hand) -> Hand {
+pub fn get_winnings(s: &str) -> u32 {
+    winnings(&parse_all_hands(s))
+}
+
+pub fn use_joker(hand: &Hand) -> Hand {
     let mut new_hand = hand.clone();
 
     let num_jokers = hand.cards.chars().filter(|c| *c == 'J').count();
@@ -177,8 +194,8 @@ fn use_joker(hand: &Hand) -> Hand {
     new_hand
 }
 
-pub fn get_winnings_with_jokers(s: &str) -> u32 {
-    let mut sorted_hands_with_bids = parse_all_hands(s)
+pub fn winnings_with_jokers(hands_with_bids: &[HandWithBid]) -> u32 {
+    let mut sorted_hands_with_bids = hands_with_bids
         .iter()
         .map(|hand_with_bid| HandWithBid {
             hand: use_joker(&hand_with_bid.hand),
@@ -189,6 +206,36 @@ pub fn get_winnings_with_jokers(s: &str) -> u32 {
     calc_winnings(&sorted_hands_with_bids)
 }
 
+pub fn get_winnings_with_jokers(s: &str) -> u32 {
+    winnings_with_jokers(&parse_all_hands(s))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<HandWithBid>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_all_hands(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        winnings(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        winnings_with_jokers(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|hand| format!("{hand:#?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day07);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,80 @@
+use aoc2023_utils::Rng;
+use std::collections::HashSet;
+
+// Number of hands.
+pub const DEFAULT_SIZE: usize = 1000;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+// Winnings are a u32 sum of rank * bid, which even with every bid at 1 is
+// n * (n + 1) / 2. This is the biggest n that fits, well short of the 13^5
+// distinct hands. Bigger sizes get clamped to it.
+pub const MAX_SIZE: usize = 92_681;
+
+const MAX_BID: u64 = 1000;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.min(MAX_SIZE);
+
+    // Shrink the bids for big sizes to keep the winnings in range.
+    let rank_sum = (size as u64 * (size as u64 + 1) / 2).max(1);
+    let max_bid = MAX_BID.min(u32::MAX as u64 / rank_sum);
+
+    let mut seen = HashSet::new();
+    let mut hands = String::new();
+    while seen.len() < size {
+        let hand = (0..5)
+            .map(|_| *rng.choose(CARDS) as char)
+            .collect::<String>();
+        if seen.insert(hand.clone()) {
+            hands += &format!("{hand} {}\n", rng.below(max_bid) + 1);
+        }
+    }
+
+    hands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{winnings, winnings_with_jokers, Day07, HandWithBid};
+    use aoc2023_utils::Solution;
+
+    // Each part ranks the hands 1..=n in some order, so its winnings are
+    // somewhere between every bid taken once and every bid taken n times.
+    fn check_winnings(parsed: &[HandWithBid]) {
+        let bid_sum = parsed.iter().map(|hand| hand.bid as u64).sum::<u64>();
+        let bounds = bid_sum..=bid_sum * parsed.len() as u64;
+        assert!(bounds.contains(&(winnings(parsed) as u64)));
+        assert!(bounds.contains(&(winnings_with_jokers(parsed) as u64)));
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let input = generate(3, 50);
+        assert_eq!(input.lines().count(), 50);
+
+        let parsed = Day07.parse(&input);
+        let hands = parsed
+            .iter()
+            .map(|hand| hand.hand.cards().to_string())
+            .collect::<HashSet<String>>();
+        assert_eq!(hands.len(), 50);
+        assert!(parsed
+            .iter()
+            .all(|hand| (1..=MAX_BID).contains(&(hand.bid as u64))));
+        check_winnings(&parsed);
+    }
+
+    #[test]
+    fn test_generate_keeps_winnings_in_range() {
+        check_winnings(&Day07.parse(&generate(4, 5000)));
+
+        let rank_sum = |size: u64| size * (size + 1) / 2;
+        assert!(rank_sum(MAX_SIZE as u64) <= u32::MAX as u64);
+        assert!(rank_sum(MAX_SIZE as u64 + 1) > u32::MAX as u64);
+    }
+}
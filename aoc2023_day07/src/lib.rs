@@ -0,0 +1,8 @@
+mod day07;
+pub mod generate;
+
+pub use day07::{
+    cmp_hand, cmp_hand_j, get_winnings, get_winnings_with_jokers, parse_all_hands, parse_hand,
+    parse_hand_with_bid, use_joker, winnings, winnings_with_jokers, Day07, Hand, HandKind,
+    HandWithBid,
+};
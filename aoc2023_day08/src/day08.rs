@@ -1,15 +1,16 @@
+use aoc2023_utils::{cancel, log_debug, math, Solution};
 use std::collections::HashMap;
 
-type Tree = HashMap<String, (String, String)>;
+pub type Tree = HashMap<String, (String, String)>;
 
-fn parse_input(s: &str) -> (String, Tree) {
+pub fn parse_input(s: &str) -> (String, Tree) {
     let mut lines = s.lines();
     let directions = lines.next().unwrap();
     lines.next();
 
     let mut tree = Tree::default();
 
-    while let Some(line) = lines.next() {
+    for line in lines {
         let node_name = &line[0..3];
         let l_node = &line[7..10];
         let r_node = &line[12..15];
@@ -22,17 +23,16 @@ fn parse_input(s: &str) -> (String, Tree) {
     (directions.to_string(), tree)
 }
 
-pub fn get_traversal_steps(s: &str) -> u32 {
-    let (directions, tree) = parse_input(s);
-
+pub fn traversal_steps(directions: &str, tree: &Tree) -> u32 {
     let directions = directions.as_bytes();
 
-    let mut steps = 0 as usize;
+    let mut steps = 0_usize;
     let mut current = "AAA";
 
     while current != "ZZZ" {
+        cancel::check();
         let idx = steps % directions.len();
-        if directions[idx] == ('L' as u8) {
+        if directions[idx] == b'L' {
             current = &tree[current].0;
         } else {
             current = &tree[current].1;
@@ -44,42 +44,17 @@ pub fn get_traversal_steps(s: &str) -> u32 {
     steps as u32
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        a
-    } else if a < b {
-        gcd(b, a)
-    } else {
-        gcd(b, a % b)
-    }
-}
-
-fn lcm(a: usize, b: usize) -> usize {
-    (a * b) / gcd(a, b)
-}
-
-fn lcm_nums(nums: &Vec<usize>) -> usize {
-    let mut result = nums[0];
-
-    let mut idx = 1 as usize;
-    while idx < nums.len() {
-        result = lcm(result, nums[idx]);
-        idx += 1;
-    }
-
-    println!("result: {result}");
-
-    result
-}
-
-pub fn get_ghost_traversal_steps(s: &str) -> u64 {
+pub fn get_traversal_steps(s: &str) -> u32 {
     let (directions, tree) = parse_input(s);
+    traversal_steps(&directions, &tree)
+}
 
+pub fn ghost_traversal_steps(directions: &str, tree: &Tree) -> u64 {
     let directions = directions.as_bytes();
 
     let start_nodes = tree
         .keys()
-        .filter(|key| key.chars().last().unwrap() == 'A')
+        .filter(|key| key.ends_with('A'))
         .map(|key| &key[..])
         .collect::<Vec<&str>>();
 
@@ -87,10 +62,11 @@ pub fn get_ghost_traversal_steps(s: &str) -> u64 {
         .iter()
         .map(|node| {
             let mut current = *node;
-            let mut steps = 0 as usize;
-            while current.chars().last().unwrap() != 'Z' {
+            let mut steps = 0_usize;
+            while !current.ends_with('Z') {
+                cancel::check();
                 let idx = steps % directions.len();
-                let go_left = directions[idx] == ('L' as u8);
+                let go_left = directions[idx] == b'L';
                 current = if go_left {
                     &tree[current].0[..]
                 } else {
@@ -104,11 +80,38 @@ pub fn get_ghost_traversal_steps(s: &str) -> u64 {
         })
         .collect::<Vec<usize>>();
 
-    println!("cycle_lens: {:?}", cycle_lens);
+    log_debug!("cycle_lens: {:?}", cycle_lens);
+
+    math::lcm_all(cycle_lens).expect("lcm of cycle lengths overflowed") as u64
+}
 
-    lcm_nums(&cycle_lens) as u64
+pub fn get_ghost_traversal_steps(s: &str) -> u64 {
+    let (directions, tree) = parse_input(s);
+    ghost_traversal_steps(&directions, &tree)
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = (String, Tree);
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        traversal_steps(&parsed.0, &parsed.1)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        ghost_traversal_steps(&parsed.0, &parsed.1)
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day08);
+
 #[cfg(test)]
 mod tests {
     use super::*;
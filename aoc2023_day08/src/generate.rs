@@ -0,0 +1,144 @@
+use aoc2023_utils::Rng;
+use std::collections::HashSet;
+
+// Length of the direction list.
+pub const DEFAULT_SIZE: usize = 283;
+
+const NUM_GHOSTS: usize = 6;
+// Three letter names that don't end in A or Z run out at 26 * 26 * 24.
+const MAX_NODES: usize = 15_000;
+// Every ghost loops at least once through the directions, so they can't be
+// longer than this. Bigger sizes get clamped to it.
+pub const MAX_SIZE: usize = MAX_NODES / NUM_GHOSTS;
+const PRIMES: [usize; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+struct Names {
+    used: HashSet<String>,
+}
+
+impl Names {
+    fn gen(&mut self, rng: &mut Rng, last: Option<u8>) -> String {
+        loop {
+            let mut name = [*rng.choose(LETTERS), *rng.choose(LETTERS), 0];
+            name[2] = match last {
+                Some(last) => last,
+                None => *rng.choose(&LETTERS[1..25]),
+            };
+            let name = String::from_utf8(name.to_vec()).unwrap();
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+// Lays out one ghost's path the way the real inputs do: the start node leads
+// into a loop of `len` steps ending on the Z node, which has the same exits as
+// the start node, so the ghost hits Z every `len` steps. Exits that the
+// directions never take point somewhere random on the same path.
+fn gen_ghost(
+    rng: &mut Rng,
+    names: &mut Names,
+    directions: &[u8],
+    len: usize,
+    start: String,
+    end: String,
+) -> Vec<(String, String, String)> {
+    let mut path = vec![start];
+    path.extend((1..len).map(|_| names.gen(rng, None)));
+    path.push(end);
+
+    let mut nodes = (0..len)
+        .map(|pos| {
+            let next = path[pos + 1].clone();
+            let other = rng.choose(&path[..len]).clone();
+            let (left, right) = if directions[pos % directions.len()] == b'L' {
+                (next, other)
+            } else {
+                (other, next)
+            };
+            (path[pos].clone(), left, right)
+        })
+        .collect::<Vec<(String, String, String)>>();
+    nodes.push((path[len].clone(), nodes[0].1.clone(), nodes[0].2.clone()));
+    nodes
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let num_dirs = size.clamp(1, MAX_SIZE);
+    let directions = (0..num_dirs)
+        .map(|_| if rng.chance(0.5) { b'L' } else { b'R' })
+        .collect::<Vec<u8>>();
+
+    // Each ghost loops every num_dirs * prime steps, so part 2's answer is
+    // num_dirs times the product of the primes.
+    let max_prime = MAX_NODES / (NUM_GHOSTS * num_dirs);
+    let mut primes = PRIMES
+        .into_iter()
+        .filter(|prime| *prime <= max_prime)
+        .collect::<Vec<usize>>();
+    rng.shuffle(&mut primes);
+    primes.resize(NUM_GHOSTS, 1);
+
+    let mut names = Names {
+        used: HashSet::from(["AAA".to_string(), "ZZZ".to_string()]),
+    };
+    let mut nodes = vec![];
+    for (ghost_idx, prime) in primes.into_iter().enumerate() {
+        let (start, end) = if ghost_idx == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (
+                names.gen(&mut rng, Some(b'A')),
+                names.gen(&mut rng, Some(b'Z')),
+            )
+        };
+        nodes.extend(gen_ghost(
+            &mut rng,
+            &mut names,
+            &directions,
+            num_dirs * prime,
+            start,
+            end,
+        ));
+    }
+    rng.shuffle(&mut nodes);
+
+    let mut network = String::from_utf8(directions).unwrap() + "\n\n";
+    for (node, left, right) in nodes {
+        network += &format!("{node} = ({left}, {right})\n");
+    }
+    network
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day08;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 20), generate(1, 20));
+        assert_ne!(generate(1, 20), generate(2, 20));
+
+        let parsed = Day08.parse(&generate(3, 20));
+        assert_eq!(parsed.0.len(), 20);
+        assert_eq!(parsed.1.keys().filter(|key| key.ends_with('A')).count(), 6);
+        assert_eq!(Day08.part1(&parsed) % 20, 0);
+
+        // Six distinct primes out of those up to 15000 / (6 * 20).
+        let part2 = Day08.part2(&parsed);
+        assert_eq!(part2 % 20, 0);
+        assert!(part2 / 20 >= 2 * 3 * 5 * 7 * 11 * 13);
+    }
+
+    #[test]
+    fn test_generate_long_directions() {
+        let parsed = Day08.parse(&generate(4, MAX_SIZE));
+        assert_eq!(parsed.0.len(), MAX_SIZE);
+        assert_eq!(Day08.part2(&parsed), MAX_SIZE as u64);
+    }
+}
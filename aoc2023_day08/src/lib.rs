@@ -0,0 +1,7 @@
+mod day08;
+pub mod generate;
+
+pub use day08::{
+    get_ghost_traversal_steps, get_traversal_steps, ghost_traversal_steps, parse_input,
+    traversal_steps, Day08, Tree,
+};
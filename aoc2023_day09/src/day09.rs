@@ -1,14 +1,15 @@
-fn parse_line(line: &str) -> Vec<i64> {
+use aoc2023_utils::Solution;
+
+pub fn parse_line(line: &str) -> Vec<i64> {
     line.split(' ')
         .map(|part| part.parse().unwrap())
         .collect::<Vec<i64>>()
 }
 
-fn get_line_diffs(vals: &Vec<i64>) -> Vec<i64> {
-    let mut new_vals = Vec::<i64>::default();
-    new_vals.reserve(vals.len() - 1);
+fn get_line_diffs(vals: &[i64]) -> Vec<i64> {
+    let mut new_vals = Vec::<i64>::with_capacity(vals.len() - 1);
 
-    let mut idx = 1 as usize;
+    let mut idx = 1_usize;
     while idx < vals.len() {
         new_vals.push(vals[idx] - vals[idx - 1]);
         idx += 1;
@@ -17,12 +18,12 @@ fn get_line_diffs(vals: &Vec<i64>) -> Vec<i64> {
     new_vals
 }
 
-fn all_zeros(vals: &Vec<i64>) -> bool {
+fn all_zeros(vals: &[i64]) -> bool {
     vals.iter().all(|val| *val == 0)
 }
 
-fn extrapolate_line(vals: &Vec<i64>) -> i64 {
-    let mut value_sets = vec![vals.clone()];
+pub fn extrapolate_line(vals: &[i64]) -> i64 {
+    let mut value_sets = vec![vals.to_vec()];
 
     loop {
         value_sets.push(get_line_diffs(value_sets.last().unwrap()));
@@ -38,8 +39,8 @@ fn extrapolate_line(vals: &Vec<i64>) -> i64 {
         .sum()
 }
 
-fn extrapolate_line_back(vals: &Vec<i64>) -> i64 {
-    let mut value_sets = vec![vals.clone()];
+pub fn extrapolate_line_back(vals: &[i64]) -> i64 {
+    let mut value_sets = vec![vals.to_vec()];
 
     loop {
         value_sets.push(get_line_diffs(value_sets.last().unwrap()));
@@ -64,29 +65,58 @@ fn extrapolate_line_back(vals: &Vec<i64>) -> i64 {
     result
 }
 
+pub fn parse_input(s: &str) -> Vec<Vec<i64>> {
+    s.lines().map(parse_line).collect()
+}
+
+pub fn sum_extrapolated(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|vals| extrapolate_line(vals)).sum()
+}
+
 pub fn sum_extrapolated_values(s: &str) -> i64 {
-    s.lines()
-        .map(|line| parse_line(line))
-        .map(|vals| extrapolate_line(&vals))
-        .sum()
+    sum_extrapolated(&parse_input(s))
+}
+
+pub fn sum_extrapolated_back(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|vals| extrapolate_line_back(vals)).sum()
 }
 
 pub fn sum_extrapolated_back_values(s: &str) -> i64 {
-    s.lines()
-        .map(|line| parse_line(line))
-        .map(|vals| extrapolate_line_back(&vals))
-        .sum()
+    sum_extrapolated_back(&parse_input(s))
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_extrapolated(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_extrapolated_back(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|line| format!("{line:?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day09);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const SAMPLE_INPUT: &str = concat!(
-        "0 3 6 9 12 15\n",
-        "1 3 6 10 15 21\n",
-        "10 13 16 21 30 45\n",
-    );
+    const SAMPLE_INPUT: &str =
+        concat!("0 3 6 9 12 15\n", "1 3 6 10 15 21\n", "10 13 16 21 30 45\n",);
 
     #[test]
     fn test_parse_line() {
@@ -95,22 +125,19 @@ mod tests {
 
     #[test]
     fn test_get_line_diffs() {
-        assert_eq!(
-            get_line_diffs(&vec![0, 3, 6, 9, 12, 15]),
-            vec![3, 3, 3, 3, 3]
-        );
+        assert_eq!(get_line_diffs(&[0, 3, 6, 9, 12, 15]), vec![3, 3, 3, 3, 3]);
     }
 
     #[test]
     fn test_extrapolate_line() {
-        assert_eq!(extrapolate_line(&vec![0, 3, 6, 9, 12, 15]), 18);
-        assert_eq!(extrapolate_line(&vec![1, 3, 6, 10, 15, 21]), 28);
-        assert_eq!(extrapolate_line(&vec![10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(extrapolate_line(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate_line(&[1, 3, 6, 10, 15, 21]), 28);
+        assert_eq!(extrapolate_line(&[10, 13, 16, 21, 30, 45]), 68);
     }
 
     #[test]
     fn test_extrapolate_line_back() {
-        assert_eq!(extrapolate_line_back(&vec![10, 13, 16, 21, 30, 45]), 5);
+        assert_eq!(extrapolate_line_back(&[10, 13, 16, 21, 30, 45]), 5);
     }
 
     #[test]
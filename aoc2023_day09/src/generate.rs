@@ -0,0 +1,77 @@
+use aoc2023_utils::{math, Rng};
+
+// Number of sequences.
+pub const DEFAULT_SIZE: usize = 200;
+
+const SEQ_LEN: usize = 21;
+const MAX_DEGREE: usize = 8;
+const MAX_COEFF: i64 = 10;
+
+// Sequences are polynomials in the index, written in the binomial basis so
+// integer coefficients always give integer values. A polynomial of degree d
+// bottoms out in zeros after d + 1 rounds of differences.
+fn gen_sequence(rng: &mut Rng) -> Vec<i64> {
+    let degree = rng.range(0..MAX_DEGREE + 1);
+    let coeffs = (0..=degree)
+        .map(|_| rng.range(0..(2 * MAX_COEFF + 1) as usize) as i64 - MAX_COEFF)
+        .collect::<Vec<i64>>();
+
+    (0..SEQ_LEN as i64)
+        .map(|x| {
+            coeffs
+                .iter()
+                .enumerate()
+                .map(|(k, coeff)| coeff * math::binomial(x, k as i64).unwrap())
+                .sum()
+        })
+        .collect()
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            let seq = gen_sequence(&mut rng)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>();
+            seq.join(" ") + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day09;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let parsed = Day09.parse(&generate(3, 50));
+        assert_eq!(parsed.len(), 50);
+        assert!(parsed.iter().all(|seq| seq.len() == SEQ_LEN));
+
+        // Going back is going forward through the reversed sequences.
+        let reversed = parsed
+            .iter()
+            .map(|seq| seq.iter().rev().copied().collect())
+            .collect::<Vec<Vec<i64>>>();
+        assert_eq!(Day09.part2(&parsed), Day09.part1(&reversed));
+    }
+
+    #[test]
+    fn test_gen_sequence_is_polynomial() {
+        let mut rng = Rng::new(4);
+        for _ in 0..20 {
+            let mut seq = gen_sequence(&mut rng);
+            for _ in 0..=MAX_DEGREE {
+                seq = seq.windows(2).map(|w| w[1] - w[0]).collect();
+            }
+            assert!(seq.iter().all(|val| *val == 0));
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod day09;
+pub mod generate;
+
+pub use day09::{
+    extrapolate_line, extrapolate_line_back, parse_input, parse_line, sum_extrapolated,
+    sum_extrapolated_back, sum_extrapolated_back_values, sum_extrapolated_values, Day09,
+};
@@ -1,6 +1,8 @@
+use aoc2023_utils::{log_debug, Coord, Grid, Image, Rgb, Solution};
+
 #[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
 #[repr(usize)]
-enum Pipe {
+pub enum Pipe {
     None = 0,
     NorthSouth = 1,
     EastWest = 2,
@@ -19,15 +21,14 @@ enum CardinalDir {
     West = 3,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
-struct Coord {
-    row: usize,
-    col: usize,
-}
-
-type PipeMap = Vec<Vec<Pipe>>;
+pub type PipeMap = Vec<Vec<Pipe>>;
 type DrawnMap = Vec<Vec<u8>>;
 
+const LOOP_COLOR: Rgb = [255, 200, 0];
+const INSIDE_COLOR: Rgb = [0, 160, 255];
+const JUNK_PIPE_COLOR: Rgb = [90, 90, 90];
+const OUTSIDE_COLOR: Rgb = [20, 20, 20];
+
 fn symbol_to_pipe(c: char) -> Pipe {
     match c {
         '|' => Pipe::NorthSouth,
@@ -51,59 +52,47 @@ fn get_neighbors(pipe_map: &PipeMap, coord: Coord) -> [Pipe; 4] {
 }
 
 fn is_connected_from_north(neighbors: &[Pipe; 4]) -> bool {
-    match neighbors[CardinalDir::North as usize] {
-        Pipe::NorthSouth | Pipe::SouthEast | Pipe::SouthWest => true,
-        _ => false,
-    }
+    matches!(
+        neighbors[CardinalDir::North as usize],
+        Pipe::NorthSouth | Pipe::SouthEast | Pipe::SouthWest
+    )
 }
 
 fn is_connected_from_south(neighbors: &[Pipe; 4]) -> bool {
-    match neighbors[CardinalDir::South as usize] {
-        Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest => true,
-        _ => false,
-    }
+    matches!(
+        neighbors[CardinalDir::South as usize],
+        Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest
+    )
 }
 
 fn is_connected_from_east(neighbors: &[Pipe; 4]) -> bool {
-    match neighbors[CardinalDir::East as usize] {
-        Pipe::EastWest | Pipe::NorthWest | Pipe::SouthWest => true,
-        _ => false,
-    }
+    matches!(
+        neighbors[CardinalDir::East as usize],
+        Pipe::EastWest | Pipe::NorthWest | Pipe::SouthWest
+    )
 }
 
 fn is_connected_from_west(neighbors: &[Pipe; 4]) -> bool {
-    match neighbors[CardinalDir::West as usize] {
-        Pipe::EastWest | Pipe::NorthEast | Pipe::SouthEast => true,
-        _ => false,
-    }
+    matches!(
+        neighbors[CardinalDir::West as usize],
+        Pipe::EastWest | Pipe::NorthEast | Pipe::SouthEast
+    )
 }
 
 fn connects_north(pipe: Pipe) -> bool {
-    match pipe {
-        Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest => true,
-        _ => false,
-    }
+    matches!(pipe, Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest)
 }
 
 fn connects_south(pipe: Pipe) -> bool {
-    match pipe {
-        Pipe::NorthSouth | Pipe::SouthEast | Pipe::SouthWest => true,
-        _ => false,
-    }
+    matches!(pipe, Pipe::NorthSouth | Pipe::SouthEast | Pipe::SouthWest)
 }
 
 fn connects_east(pipe: Pipe) -> bool {
-    match pipe {
-        Pipe::EastWest | Pipe::NorthEast | Pipe::SouthEast => true,
-        _ => false,
-    }
+    matches!(pipe, Pipe::EastWest | Pipe::NorthEast | Pipe::SouthEast)
 }
 
 fn connects_west(pipe: Pipe) -> bool {
-    match pipe {
-        Pipe::EastWest | Pipe::NorthWest | Pipe::SouthWest => true,
-        _ => false,
-    }
+    matches!(pipe, Pipe::EastWest | Pipe::NorthWest | Pipe::SouthWest)
 }
 
 fn derive_pipe(pipe_map: &PipeMap, coord: Coord) -> Pipe {
@@ -132,12 +121,11 @@ fn derive_pipe(pipe_map: &PipeMap, coord: Coord) -> Pipe {
 
 fn parse_row(row: &str) -> (Vec<Pipe>, Option<usize>) {
     let mut start_idx: Option<usize> = None;
-    let mut pipes = Vec::<Pipe>::default();
-    pipes.reserve(row.len() + 2);
+    let mut pipes = Vec::<Pipe>::with_capacity(row.len() + 2);
     pipes.push(Pipe::None);
     for i in 0..row.len() {
         let c = row.as_bytes()[i];
-        let pipe = if c == 'S' as u8 {
+        let pipe = if c == b'S' {
             start_idx = Some(i + 1);
             Pipe::None
         } else {
@@ -150,7 +138,7 @@ fn parse_row(row: &str) -> (Vec<Pipe>, Option<usize>) {
     (pipes, start_idx)
 }
 
-fn parse_input(s: &str) -> (PipeMap, Coord) {
+pub fn parse_input(s: &str) -> (PipeMap, Coord) {
     let mut start_coord = Coord { row: 0, col: 0 };
     let mut pipe_map = PipeMap::default();
     s.lines().enumerate().for_each(|(row_idx, line)| {
@@ -159,9 +147,9 @@ fn parse_input(s: &str) -> (PipeMap, Coord) {
         }
 
         let (row, start_col_idx_opt) = parse_row(line);
-        if start_col_idx_opt.is_some() {
+        if let Some(start_col_idx) = start_col_idx_opt {
             start_coord.row = row_idx + 1;
-            start_coord.col = start_col_idx_opt.unwrap();
+            start_coord.col = start_col_idx;
         }
 
         pipe_map.push(row);
@@ -208,8 +196,8 @@ fn get_next_pipe_coord(pipe_map: &PipeMap, at: Coord, prev: Coord) -> Coord {
     }
 }
 
-fn get_max_dist(pipe_map: &PipeMap, start: Coord) -> usize {
-    let mut steps = 0 as usize;
+pub fn get_max_dist(pipe_map: &PipeMap, start: Coord) -> usize {
+    let mut steps = 0_usize;
 
     let mut at = start;
     let mut prev = start;
@@ -236,24 +224,27 @@ pub fn get_max_dist_from_input(s: &str) -> usize {
 fn gen_clear_drawn_map(pipe_map: &PipeMap) -> DrawnMap {
     pipe_map
         .iter()
-        .map(|row| vec!['.' as u8; row.len()])
+        .map(|row| vec![b'.'; row.len()])
         .collect::<DrawnMap>()
 }
 
-fn draw_map(drawn_map: &DrawnMap) {
-    drawn_map.iter().for_each(|row| {
-        println!("{}", String::from_utf8(row.clone()).unwrap());
-    });
+fn drawn_map_to_str(drawn_map: &DrawnMap) -> String {
+    drawn_map
+        .iter()
+        .map(|row| String::from_utf8(row.clone()).unwrap())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-fn get_num_enclosing_tiles(pipe_map: &PipeMap, start: Coord) -> usize {
+// Marks the loop with '*' and the tiles it encloses with 'I'.
+fn draw_map(pipe_map: &PipeMap, start: Coord) -> DrawnMap {
     let mut drawn_map = gen_clear_drawn_map(pipe_map);
 
     let mut at = start;
     let mut prev = start;
 
     loop {
-        drawn_map[at.row][at.col] = '*' as u8;
+        drawn_map[at.row][at.col] = b'*';
         let next = get_next_pipe_coord(pipe_map, at, prev);
         if next == start {
             break;
@@ -263,8 +254,6 @@ fn get_num_enclosing_tiles(pipe_map: &PipeMap, start: Coord) -> usize {
         at = next;
     }
 
-    let mut num_inside_spaces = 0 as usize;
-
     let num_rows = pipe_map.len();
     let num_cols = pipe_map[0].len();
 
@@ -281,13 +270,12 @@ fn get_num_enclosing_tiles(pipe_map: &PipeMap, start: Coord) -> usize {
 
             let pipe = pipe_map[row_idx][col_idx as usize];
             let drawn = drawn_map[row_idx][col_idx as usize];
-            let is_open = drawn == '.' as u8;
+            let is_open = drawn == b'.';
             let ignore_pipe = (pipe == Pipe::NorthEast) || (pipe == Pipe::SouthWest);
-            let is_part_of_loop = drawn == '*' as u8;
+            let is_part_of_loop = drawn == b'*';
 
             if is_open && inside {
-                num_inside_spaces += 1;
-                drawn_map[row_idx][col_idx as usize] = 'I' as u8;
+                drawn_map[row_idx][col_idx as usize] = b'I';
             } else if is_part_of_loop && !ignore_pipe {
                 inside = !inside;
             }
@@ -296,9 +284,32 @@ fn get_num_enclosing_tiles(pipe_map: &PipeMap, start: Coord) -> usize {
         top_col_idx += 1;
     }
 
-    draw_map(&drawn_map);
+    drawn_map
+}
+
+pub fn get_num_enclosing_tiles(pipe_map: &PipeMap, start: Coord) -> usize {
+    let drawn_map = draw_map(pipe_map, start);
+    log_debug!("drawn map:\n{}", drawn_map_to_str(&drawn_map));
+
+    drawn_map
+        .iter()
+        .flatten()
+        .filter(|tile| **tile == b'I')
+        .count()
+}
 
-    num_inside_spaces
+pub fn render(pipe_map: &PipeMap, start: Coord) -> Image {
+    let tiles = draw_map(pipe_map, start)
+        .into_iter()
+        .zip(pipe_map)
+        .map(|(drawn_row, pipe_row)| drawn_row.into_iter().zip(pipe_row.clone()).collect())
+        .collect();
+    Image::from_grid(&Grid::from_rows(tiles), |tile| match tile {
+        (b'*', _) => LOOP_COLOR,
+        (b'I', _) => INSIDE_COLOR,
+        (_, Pipe::None) => OUTSIDE_COLOR,
+        _ => JUNK_PIPE_COLOR,
+    })
 }
 
 pub fn get_num_enclosing_tiles_from_input(s: &str) -> usize {
@@ -306,6 +317,32 @@ pub fn get_num_enclosing_tiles_from_input(s: &str) -> usize {
     get_num_enclosing_tiles(&pipe_map, start)
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = (PipeMap, Coord);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        get_max_dist(&parsed.0, parsed.1)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        get_num_enclosing_tiles(&parsed.0, parsed.1)
+    }
+
+    fn render(&self, parsed: &Self::Parsed) -> Option<Image> {
+        Some(render(&parsed.0, parsed.1))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day10);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,60 +369,22 @@ mod tests {
         assert_eq!(get_max_dist_from_input(SAMPLE_INPUT2), 8);
     }
 
-    // #[test]
-    // fn test_get_max_dist_from_real_input() {
-    //     println!("CWD: {:?}", std::env::current_dir().unwrap());
-    //     let input = aoc2023_utils::get_input("../inputs/day10.txt");
-    //     assert_eq!(get_max_dist_from_input(&input), 4);
-    // }
-
-    // const SAMPLE_INPUT3: &str = concat!(
-    //     "...........\n",
-    //     ".S-------7.\n",
-    //     ".|F-----7|.\n",
-    //     ".||.....||.\n",
-    //     ".||.....||.\n",
-    //     ".|L-7.F-J|.\n",
-    //     ".|..|.|..|.\n",
-    //     ".L--J.L--J.\n",
-    //     "...........\n",
-    // );
-
-    // const SAMPLE_INPUT4: &str = concat!(
-    //     ".F----7F7F7F7F-7....\n",
-    //     ".|F--7||||||||FJ....\n",
-    //     ".||.FJ||||||||L7....\n",
-    //     "FJL7L7LJLJ||LJ.L-7..\n",
-    //     "L--J.L7...LJS7F-7L7.\n",
-    //     "....F-J..F7FJ|L7L7L7\n",
-    //     "....L7.F7||L7|.L7L7|\n",
-    //     ".....|FJLJ|FJ|F7|.LJ\n",
-    //     "....FJL-7.||.||||...\n",
-    //     "....L---J.LJ.LJLJ...\n",
-    // );
-
-    // const SAMPLE_INPUT5: &str = concat!(
-    //     "FF7FSF7F7F7F7F7F---7\n",
-    //     "L|LJ||||||||||||F--J\n",
-    //     "FL-7LJLJ||||||LJL-77\n",
-    //     "F--JF--7||LJLJ7F7FJ-\n",
-    //     "L---JF-JLJ.||-FJLJJ7\n",
-    //     "|F|F-JF---7F7-L7L|7|\n",
-    //     "|FFJF7L7F-JF7|JL---7\n",
-    //     "7-L-JL7||F7|L7F-7F7|\n",
-    //     "L.L7LFJ|||||FJL7||LJ\n",
-    //     "L7JLJL-JLJLJL--JLJ.L\n",
-    // );
-
-    // #[test]
-    // fn test_get_line_diffs() {
-    //     let (pipe_map, start) = parse_input(SAMPLE_INPUT5);
-
-    //     let result = get_num_enclosing_tiles(&pipe_map, start);
-    //     println!("result: {}", result);
-
-    //     // let drawn_map = gen_clear_drawn_map(&pipe_map);
-    //     // draw_map(&drawn_map);
-    //     // assert!(false);
-    // }
+    #[test]
+    fn test_render() {
+        let (pipe_map, start) = parse_input(SAMPLE_INPUT1);
+        let image = render(&pipe_map, start);
+        // The map is padded by a tile all round.
+        assert_eq!((image.width(), image.height()), (7, 7));
+        assert_eq!(image.get(2, 2), LOOP_COLOR);
+        assert_eq!(image.get(3, 3), INSIDE_COLOR);
+        assert_eq!(image.get(1, 1), OUTSIDE_COLOR);
+    }
+
+    #[test]
+    fn test_real_input() {
+        let input =
+            aoc2023_utils::get_input(concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs/day10.txt"));
+        assert_eq!(get_max_dist_from_input(&input), 6838);
+        assert_eq!(get_num_enclosing_tiles_from_input(&input), 451);
+    }
 }
@@ -0,0 +1,294 @@
+use aoc2023_utils::Rng;
+
+// Size of the blob the loop goes around. The map is 2 * size + 1 tiles square.
+pub const DEFAULT_SIZE: usize = 70;
+
+const NORTH: u8 = 1;
+const SOUTH: u8 = 2;
+const EAST: u8 = 4;
+const WEST: u8 = 8;
+const JUNK: &[u8] = b"|-LJ7F";
+
+// The 8 cells around a cell, in order around the ring.
+const RING: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+struct Blob {
+    size: usize,
+    cells: Vec<Vec<bool>>,
+}
+
+impl Blob {
+    fn has(&self, row: usize, col: usize, (d_row, d_col): (isize, isize)) -> bool {
+        match (row.checked_add_signed(d_row), col.checked_add_signed(d_col)) {
+            (Some(row), Some(col)) if row < self.size && col < self.size => self.cells[row][col],
+            _ => false,
+        }
+    }
+
+    // Adding a cell must keep the blob one piece with no holes and no cells
+    // touching only at a corner, so its outline is a single simple loop. That
+    // holds when the blob cells around it form one unbroken run that includes
+    // an edge neighbour, and no corner neighbour is only diagonally attached.
+    fn can_add(&self, row: usize, col: usize) -> bool {
+        if self.cells[row][col] {
+            return false;
+        }
+
+        let ring = RING.map(|offset| self.has(row, col, offset));
+        let num_runs = (0..8)
+            .filter(|idx| ring[*idx] && !ring[(idx + 7) % 8])
+            .count();
+        let touches_edge = [0, 2, 4, 6].iter().any(|idx| ring[*idx]);
+        let pinched = [1, 3, 5, 7]
+            .iter()
+            .any(|idx| ring[*idx] && !ring[idx - 1] && !ring[(idx + 1) % 8]);
+
+        num_runs == 1 && touches_edge && !pinched
+    }
+}
+
+fn gen_blob(rng: &mut Rng, size: usize) -> Blob {
+    let mut blob = Blob {
+        size,
+        cells: vec![vec![false; size]; size],
+    };
+    blob.cells[size / 2][size / 2] = true;
+
+    let target = (size * size) * rng.range(30..60) / 100;
+    let mut num_cells = 1;
+    for _ in 0..size * size * 50 {
+        if num_cells >= target {
+            break;
+        }
+        let (row, col) = (rng.range(0..size), rng.range(0..size));
+        if blob.can_add(row, col) {
+            blob.cells[row][col] = true;
+            num_cells += 1;
+        }
+    }
+
+    blob
+}
+
+// Blob cell (r, c) sits at tile (2r + 1, 2c + 1), and its outline runs along
+// the even rows and columns between cells.
+fn trace_outline(blob: &Blob) -> Vec<Vec<u8>> {
+    let dim = 2 * blob.size + 1;
+    let mut exits = vec![vec![0_u8; dim]; dim];
+
+    for row in 0..blob.size {
+        for col in 0..blob.size {
+            if !blob.cells[row][col] {
+                continue;
+            }
+            let (r, c) = (2 * row, 2 * col);
+            if !blob.has(row, col, (-1, 0)) {
+                exits[r][c] |= EAST;
+                exits[r][c + 1] |= EAST | WEST;
+                exits[r][c + 2] |= WEST;
+            }
+            if !blob.has(row, col, (1, 0)) {
+                exits[r + 2][c] |= EAST;
+                exits[r + 2][c + 1] |= EAST | WEST;
+                exits[r + 2][c + 2] |= WEST;
+            }
+            if !blob.has(row, col, (0, -1)) {
+                exits[r][c] |= SOUTH;
+                exits[r + 1][c] |= NORTH | SOUTH;
+                exits[r + 2][c] |= NORTH;
+            }
+            if !blob.has(row, col, (0, 1)) {
+                exits[r][c + 2] |= SOUTH;
+                exits[r + 1][c + 2] |= NORTH | SOUTH;
+                exits[r + 2][c + 2] |= NORTH;
+            }
+        }
+    }
+
+    exits
+}
+
+fn exits_to_pipe(exits: u8) -> u8 {
+    match exits {
+        x if x == NORTH | SOUTH => b'|',
+        x if x == EAST | WEST => b'-',
+        x if x == NORTH | EAST => b'L',
+        x if x == NORTH | WEST => b'J',
+        x if x == SOUTH | WEST => b'7',
+        x if x == SOUTH | EAST => b'F',
+        _ => panic!("outline tile with exits {exits:#06b}"),
+    }
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let exits = trace_outline(&gen_blob(&mut rng, size.max(1)));
+
+    let mut tiles = exits
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|exits| match exits {
+                    0 if rng.chance(0.5) => b'.',
+                    0 => *rng.choose(JUNK),
+                    exits => exits_to_pipe(*exits),
+                })
+                .collect::<Vec<u8>>()
+        })
+        .collect::<Vec<Vec<u8>>>();
+
+    let loop_tiles = exits
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, exits)| **exits != 0)
+                .map(move |(col, _)| (row, col))
+        })
+        .collect::<Vec<(usize, usize)>>();
+    let (start_row, start_col) = *rng.choose(&loop_tiles);
+    tiles[start_row][start_col] = b'S';
+
+    // The start's shape is worked out from its neighbours, so junk next to it
+    // mustn't look connected.
+    for (d_row, d_col) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        if let (Some(row), Some(col)) = (
+            start_row.checked_add_signed(d_row),
+            start_col.checked_add_signed(d_col),
+        ) {
+            if row < tiles.len() && col < tiles.len() && exits[row][col] == 0 {
+                tiles[row][col] = b'.';
+            }
+        }
+    }
+
+    tiles
+        .iter()
+        .map(|row| String::from_utf8(row.clone()).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day10;
+    use aoc2023_utils::Solution;
+
+    // Blob cells, plus the tiles on edges and corners shared only by blob
+    // cells.
+    fn count_enclosed(blob: &Blob) -> usize {
+        let mut num_enclosed = 0;
+        for row in 0..blob.size {
+            for col in 0..blob.size {
+                if !blob.cells[row][col] {
+                    continue;
+                }
+                let right = blob.has(row, col, (0, 1));
+                let down = blob.has(row, col, (1, 0));
+                let diag = blob.has(row, col, (1, 1));
+                num_enclosed += 1 + right as usize + down as usize;
+                num_enclosed += (right && down && diag) as usize;
+            }
+        }
+        num_enclosed
+    }
+
+    fn pipe_to_exits(pipe: u8) -> u8 {
+        match pipe {
+            b'|' => NORTH | SOUTH,
+            b'-' => EAST | WEST,
+            b'L' => NORTH | EAST,
+            b'J' => NORTH | WEST,
+            b'7' => SOUTH | WEST,
+            b'F' => SOUTH | EAST,
+            _ => 0,
+        }
+    }
+
+    // Follows the pipes out of S, straight off the text, and returns how long
+    // the loop is once it gets back. Panics if the pipes lead anywhere else.
+    fn walk_loop(input: &str) -> usize {
+        let tiles = input.lines().map(str::as_bytes).collect::<Vec<&[u8]>>();
+        let step = |(row, col): (usize, usize), dir: u8| match dir {
+            NORTH => (row.wrapping_sub(1), col),
+            SOUTH => (row + 1, col),
+            EAST => (row, col + 1),
+            _ => (row, col.wrapping_sub(1)),
+        };
+        let tile = |(row, col): (usize, usize)| {
+            tiles
+                .get(row)
+                .and_then(|line| line.get(col))
+                .copied()
+                .unwrap_or(b'.')
+        };
+        let opposite = |dir: u8| match dir {
+            NORTH => SOUTH,
+            SOUTH => NORTH,
+            EAST => WEST,
+            _ => EAST,
+        };
+
+        let start_row = tiles.iter().position(|line| line.contains(&b'S')).unwrap();
+        let start_col = tiles[start_row].iter().position(|c| *c == b'S').unwrap();
+        let start = (start_row, start_col);
+        let start_exits = [NORTH, SOUTH, EAST, WEST]
+            .into_iter()
+            .filter(|dir| pipe_to_exits(tile(step(start, *dir))) & opposite(*dir) != 0)
+            .collect::<Vec<u8>>();
+        assert_eq!(start_exits.len(), 2);
+
+        let (mut at, mut dir) = (step(start, start_exits[0]), start_exits[0]);
+        let mut len = 1;
+        while at != start {
+            let exits = pipe_to_exits(tile(at));
+            assert_ne!(exits & opposite(dir), 0, "dead end at {at:?}");
+            dir = exits & !opposite(dir);
+            at = step(at, dir);
+            len += 1;
+        }
+        len
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 10), generate(1, 10));
+        assert_ne!(generate(1, 10), generate(2, 10));
+
+        let input = generate(3, 10);
+        assert_eq!(input.lines().count(), 21);
+        assert_eq!(input.matches('S').count(), 1);
+
+        // The loop closes, so the furthest tile is halfway round it.
+        let loop_len = walk_loop(&input);
+        let parsed = Day10.parse(&input);
+        assert_eq!(Day10.part1(&parsed) * 2, loop_len);
+        assert!(Day10.part2(&parsed) > 0);
+    }
+
+    #[test]
+    fn test_loop_encloses_blob() {
+        for seed in 0..10 {
+            let blob = gen_blob(&mut Rng::new(seed), 8);
+            let loop_len = trace_outline(&blob)
+                .iter()
+                .flatten()
+                .filter(|exits| **exits != 0)
+                .count();
+
+            let parsed = Day10.parse(&generate(seed, 8));
+            assert_eq!(Day10.part1(&parsed), loop_len / 2);
+            assert_eq!(Day10.part2(&parsed), count_enclosed(&blob));
+        }
+    }
+}
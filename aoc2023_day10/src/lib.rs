@@ -0,0 +1,7 @@
+mod day10;
+pub mod generate;
+
+pub use day10::{
+    get_max_dist, get_max_dist_from_input, get_num_enclosing_tiles,
+    get_num_enclosing_tiles_from_input, parse_input, render, Day10, Pipe, PipeMap,
+};
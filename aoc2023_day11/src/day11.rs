@@ -1,18 +1,8 @@
-#[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
-struct Coord {
-    row: usize,
-    col: usize,
-}
-
-impl Coord {
-    fn new(row: usize, col: usize) -> Self {
-        Self { row, col }
-    }
-}
+use aoc2023_utils::{Coord, Solution};
 
-type SkyMap = Vec<Coord>;
+pub type SkyMap = Vec<Coord>;
 
-fn parse_input(s: &str) -> SkyMap {
+pub fn parse_input(s: &str) -> SkyMap {
     let mut coords = vec![];
     s.lines().enumerate().for_each(|(row_idx, line)| {
         line.chars().enumerate().for_each(|(col_idx, c)| {
@@ -48,7 +38,7 @@ fn get_expanded_rows_and_cols(sky_map: &SkyMap) -> (Vec<usize>, Vec<usize>) {
     (expanded_rows, expanded_cols)
 }
 
-fn expanded_space(sky_map: &SkyMap, expansion_factor: usize) -> SkyMap {
+pub fn expanded_space(sky_map: &SkyMap, expansion_factor: usize) -> SkyMap {
     let (expanded_rows, expanded_cols) = get_expanded_rows_and_cols(sky_map);
 
     let mut expanded_map = sky_map.clone();
@@ -84,14 +74,13 @@ fn get_pairs(map_len: usize) -> Vec<(usize, usize)> {
 }
 
 fn get_dist_between_galaxies(sky_map: &SkyMap, from: usize, to: usize) -> u64 {
-    let dy = ((sky_map[from].row as i32) - (sky_map[to].row as i32)).abs() as u64;
-    let dx = ((sky_map[from].col as i32) - (sky_map[to].col as i32)).abs() as u64;
+    let dy = ((sky_map[from].row as i32) - (sky_map[to].row as i32)).unsigned_abs() as u64;
+    let dx = ((sky_map[from].col as i32) - (sky_map[to].col as i32)).unsigned_abs() as u64;
     dy + dx
 }
 
-pub fn get_sum_of_galaxy_dists(s: &str, expansion_factor: usize) -> u64 {
-    let skymap = parse_input(s);
-    let skymap = expanded_space(&skymap, expansion_factor);
+pub fn sum_of_galaxy_dists(skymap: &SkyMap, expansion_factor: usize) -> u64 {
+    let skymap = expanded_space(skymap, expansion_factor);
 
     get_pairs(skymap.len())
         .iter()
@@ -99,6 +88,42 @@ pub fn get_sum_of_galaxy_dists(s: &str, expansion_factor: usize) -> u64 {
         .sum()
 }
 
+pub fn get_sum_of_galaxy_dists(s: &str, expansion_factor: usize) -> u64 {
+    sum_of_galaxy_dists(&parse_input(s), expansion_factor)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = SkyMap;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const PARAMS: &'static [&'static str] = &["expansion_factor"];
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_of_galaxy_dists(parsed, 2)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_of_galaxy_dists(parsed, 1000000)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|galaxy| format!("{galaxy:?}"))
+    }
+
+    fn solve_with_param(&self, parsed: &Self::Parsed, _name: &str, value: u32) -> String {
+        sum_of_galaxy_dists(parsed, value as usize).to_string()
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day11);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,67 @@
+use aoc2023_utils::Rng;
+
+// Width and height of the image.
+pub const DEFAULT_SIZE: usize = 140;
+
+const GALAXY_CHANCE: f64 = 0.02;
+const EMPTY_LINE_CHANCE: f64 = 0.1;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+
+    // Some rows and columns are kept clear so there's always something to
+    // expand.
+    let empty_rows = (0..size)
+        .map(|_| rng.chance(EMPTY_LINE_CHANCE))
+        .collect::<Vec<bool>>();
+    let empty_cols = (0..size)
+        .map(|_| rng.chance(EMPTY_LINE_CHANCE))
+        .collect::<Vec<bool>>();
+
+    let mut image = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| {
+                    let can_have_galaxy = !empty_rows[row] && !empty_cols[col];
+                    can_have_galaxy && rng.chance(GALAXY_CHANCE)
+                })
+                .collect::<Vec<bool>>()
+        })
+        .collect::<Vec<Vec<bool>>>();
+
+    // The solver needs at least one pair.
+    image[0][0] = true;
+    image[size - 1][size - 1] = true;
+
+    image
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|galaxy| if *galaxy { '#' } else { '.' })
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day11;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 30), generate(1, 30));
+        assert_ne!(generate(1, 30), generate(2, 30));
+
+        let input = generate(3, 30);
+        assert_eq!(input.lines().count(), 30);
+        assert!(input.lines().all(|line| line.len() == 30));
+
+        let parsed = Day11.parse(&input);
+        assert!(parsed.len() >= 2);
+        assert!(Day11.part2(&parsed) > Day11.part1(&parsed));
+    }
+}
@@ -0,0 +1,6 @@
+mod day11;
+pub mod generate;
+
+pub use day11::{
+    expanded_space, get_sum_of_galaxy_dists, parse_input, sum_of_galaxy_dists, Day11, SkyMap,
+};
@@ -1,37 +1,10 @@
-use chrono::Utc;
-use std::{collections::HashMap, time::Instant};
+use aoc2023_utils::memo::Memo;
+use aoc2023_utils::{math, par, Solution};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd)]
-struct SpringRow {
-    line_str: String,
-    working_segs: Vec<u64>,
-}
-
-fn n_choose_k(n: u64, k: u64) -> u64 {
-    if n < k {
-        println!("about to die with n={n}, k={k}!");
-    }
-    let mut n = n;
-    let mut k = k;
-    if k > (n - k) {
-        k = n - k;
-    }
-
-    let mut c = 1 as u64;
-    let mut i = 1 as u64;
-    while i <= k {
-        // panic on potential overflow
-        if (c / i) > (u64::MAX / n) {
-            panic!("n_choose_k OVERFLOW!!!");
-        }
-
-        c = c / i * n + c % i * n / i; // split c * n / i into (c / i * i + c % i) * n / i
-
-        i += 1;
-        n -= 1;
-    }
-
-    c
+pub struct SpringRow {
+    pub line_str: String,
+    pub working_segs: Vec<u64>,
 }
 
 fn gen_all_slot_combos(num_slots: u64, num_filled: u64) -> Vec<Vec<bool>> {
@@ -68,7 +41,7 @@ fn gen_all_slot_combos(num_slots: u64, num_filled: u64) -> Vec<Vec<bool>> {
     results
 }
 
-fn parse_spring_row(line: &str) -> SpringRow {
+pub fn parse_spring_row(line: &str) -> SpringRow {
     let mut parts = line.split(' ');
     let line_str = parts.next().unwrap().to_string();
     let working_segs = parts
@@ -79,8 +52,8 @@ fn parse_spring_row(line: &str) -> SpringRow {
         .collect::<Vec<u64>>();
 
     SpringRow {
-        line_str: line_str,
-        working_segs: working_segs,
+        line_str,
+        working_segs,
     }
 }
 
@@ -102,7 +75,7 @@ fn gen_all_possibilities(spring_row: &SpringRow) -> Vec<String> {
     seg_slot_combos
         .iter()
         .map(|seg_slot_spec| {
-            let mut seg_idx = 0 as usize;
+            let mut seg_idx = 0_usize;
             let seg_strs = seg_slot_spec
                 .iter()
                 .map(|is_filled| {
@@ -128,7 +101,7 @@ fn matches_template(s: &str, template: &str) -> bool {
     let template = template.as_bytes();
 
     for i in 0..s.len() {
-        if template[i] == '?' as u8 {
+        if template[i] == b'?' {
             continue;
         }
         if template[i] != s[i] {
@@ -139,151 +112,146 @@ fn matches_template(s: &str, template: &str) -> bool {
     true
 }
 
-fn get_num_good_configs(spring_row: &SpringRow) -> u64 {
+pub fn get_num_good_configs(spring_row: &SpringRow) -> u64 {
     gen_all_possibilities(spring_row)
         .iter()
         .filter(|opt| matches_template(opt, &spring_row.line_str))
         .count() as u64
 }
 
-fn do_get_num_good_configs2(spring_row: &SpringRow, history: &mut HashMap<String, u64>) -> u64 {
-    let history_hash = format!(
-        "{} {}",
-        spring_row.line_str,
-        spring_row
-            .working_segs
-            .iter()
-            .map(ToString::to_string)
-            .collect::<String>()
-    );
-
-    if let Some(prev_val) = history.get(&history_hash) {
-        return *prev_val;
-    }
+// Every template and list of segments the search gets to is a suffix of the
+// row it started from, so they make a key without copying anything.
+type ConfigsMemo<'a> = Memo<(&'a str, &'a [u64]), u64>;
+
+fn do_get_num_good_configs2<'a>(
+    line_str: &'a str,
+    working_segs: &'a [u64],
+    memo: &mut ConfigsMemo<'a>,
+) -> u64 {
+    memo.get_or_insert_with((line_str, working_segs), |memo| {
+        if working_segs.is_empty() {
+            if line_str.contains('#') {
+                return 0;
+            } else {
+                return 1;
+            }
+        }
 
-    if spring_row.working_segs.is_empty() {
-        if spring_row.line_str.contains('#') {
+        let template = line_str.trim_start_matches('.');
+        if template.is_empty() {
             return 0;
-        } else {
-            return 1;
         }
-    }
 
-    let template = spring_row.line_str.trim_start_matches('.');
-    if template.is_empty() {
-        return 0;
-    }
+        let mut str_to_match = vec!['#'; working_segs[0] as usize]
+            .iter()
+            .collect::<String>();
+        if (working_segs[0] as usize) < template.len() {
+            str_to_match.push('.');
+        }
 
-    let mut str_to_match = vec!['#'; spring_row.working_segs[0] as usize]
-        .iter()
-        .collect::<String>();
-    if (spring_row.working_segs[0] as usize) < template.len() {
-        str_to_match.push('.');
-    }
+        let template_len_to_check = str_to_match.len().min(template.len());
+        let can_match_front = matches_template(&str_to_match, &template[..template_len_to_check]);
+        let must_match_front = template.as_bytes()[0] == b'#';
+
+        let count_from_matched_front = if can_match_front {
+            do_get_num_good_configs2(&template[str_to_match.len()..], &working_segs[1..], memo)
+        } else {
+            0
+        };
+
+        let count_from_unmatched_front = if must_match_front {
+            0
+        } else {
+            do_get_num_good_configs2(&template[1..], working_segs, memo)
+        };
 
-    let template_len_to_check = str_to_match.len().min(template.len());
-    let can_match_front = matches_template(&str_to_match, &template[..template_len_to_check]);
-    let must_match_front = template.as_bytes()[0] == '#' as u8;
-
-    let count_from_matched_front = if can_match_front {
-        do_get_num_good_configs2(
-            &SpringRow {
-                line_str: template[str_to_match.len()..].to_string(),
-                working_segs: spring_row.working_segs[1..].into(),
-            },
-            history,
-        )
-    } else {
-        0
-    };
-
-    let count_from_unmatched_front = if must_match_front {
-        0
-    } else {
-        do_get_num_good_configs2(
-            &SpringRow {
-                line_str: template[1..].to_string(),
-                working_segs: spring_row.working_segs.clone(),
-            },
-            history,
-        )
-    };
-
-    let result = count_from_matched_front + count_from_unmatched_front;
-    history.insert(history_hash, result);
-    result
+        count_from_matched_front + count_from_unmatched_front
+    })
 }
 
-fn get_num_good_configs2(spring_row: &SpringRow) -> u64 {
+pub fn get_num_good_configs2(spring_row: &SpringRow) -> u64 {
     let row_len = spring_row.line_str.len();
     let total_filled_slots = spring_row.working_segs.iter().sum::<u64>();
     let open_slots = row_len as u64 - total_filled_slots;
     let num_seg_slots = open_slots + 1;
     let num_segs = spring_row.working_segs.len() as u64;
 
-    let num_possibilities = n_choose_k(num_seg_slots, num_segs);
-    // println!("{num_seg_slots} choose {num_segs} = {num_possibilities} possibilities");
-
     // Special case for all '?' since there's no other way to cut down on the
-    // search space.
+    // search space. Fall back to the search if the count doesn't fit.
     if spring_row.line_str.chars().all(|c| c == '?') {
-        return num_possibilities;
+        if let Some(num_possibilities) = math::binomial(num_seg_slots, num_segs) {
+            return num_possibilities;
+        }
     }
 
-    let mut history = HashMap::<String, u64>::new();
+    do_get_num_good_configs2(
+        &spring_row.line_str,
+        &spring_row.working_segs,
+        &mut Memo::new(),
+    )
+}
 
-    do_get_num_good_configs2(spring_row, &mut history)
+pub fn parse_input(s: &str) -> Vec<SpringRow> {
+    s.lines().map(parse_spring_row).collect()
 }
 
-pub fn get_sum_of_num_good_configs(s: &str) -> u64 {
-    s.lines()
-        .map(|line| parse_spring_row(line))
-        .map(|spring_row| get_num_good_configs(&spring_row))
-        .sum()
+pub fn sum_of_num_good_configs(spring_rows: &[SpringRow]) -> u64 {
+    spring_rows.iter().map(get_num_good_configs).sum()
 }
 
-fn unfold_line(s: &str) -> String {
-    let mut parts = s.split(' ');
-    let part1 = parts.next().unwrap();
-    let part2 = parts.next().unwrap();
+pub fn get_sum_of_num_good_configs(s: &str) -> u64 {
+    sum_of_num_good_configs(&parse_input(s))
+}
 
-    let part1 = vec![part1; 5].join("?");
-    let part2 = vec![part2; 5].join(",");
+pub fn unfold_spring_row(spring_row: &SpringRow) -> SpringRow {
+    SpringRow {
+        line_str: [&spring_row.line_str[..]; 5].join("?"),
+        working_segs: spring_row.working_segs.repeat(5),
+    }
+}
 
-    format!("{part1} {part2}")
+pub fn sum_of_num_good_configs_unfolded(spring_rows: &[SpringRow]) -> u64 {
+    par::map(spring_rows, |spring_row| {
+        get_num_good_configs2(&unfold_spring_row(spring_row))
+    })
+    .iter()
+    .sum()
 }
 
 pub fn get_sum_of_num_good_configs_unfolded(s: &str) -> u64 {
-    let func_start = Instant::now();
-    println!("starting at {:?}", Utc::now());
-    let mut line_num = 1;
-    let result = s
-        .lines()
-        .map(|line| unfold_line(&line))
-        .map(|line| parse_spring_row(&line))
-        .map(|spring_row| {
-            let line_start = Instant::now();
-            let result = get_num_good_configs2(&spring_row);
-            println!(
-                "finished line {line_num} at {:?} in {} sec. result = {result}",
-                Utc::now(),
-                line_start.elapsed().as_secs()
-            );
-            line_num += 1;
-            result
-        })
-        .sum();
-    println!(
-        "ended at {:?} in {} sec",
-        Utc::now(),
-        func_start.elapsed().as_secs()
-    );
-    result
+    sum_of_num_good_configs_unfolded(&parse_input(s))
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Vec<SpringRow>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_of_num_good_configs(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        sum_of_num_good_configs_unfolded(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed.get(idx).map(|spring_row| format!("{spring_row:#?}"))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day12);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc2023_utils::diff::{self, Differ};
 
     const SAMPLE_INPUT1: &str = concat!(
         "???.### 1,1,3\n",
@@ -354,14 +322,14 @@ mod tests {
     #[ignore]
     #[test]
     fn test_matches_template() {
-        assert_eq!(
-            matches_template("XXXX.######..#####.", "????.######..#####."),
-            true
-        );
-        assert_eq!(
-            matches_template("XXXX..#####..#####.", "????.######..#####."),
-            false
-        );
+        assert!(matches_template(
+            "XXXX.######..#####.",
+            "????.######..#####."
+        ));
+        assert!(!matches_template(
+            "XXXX..#####..#####.",
+            "????.######..#####."
+        ));
     }
 
     #[ignore]
@@ -520,8 +488,11 @@ mod tests {
 
     #[ignore]
     #[test]
-    fn test_unfold_line() {
-        assert_eq!(unfold_line(".# 1"), ".#?.#?.#?.#?.# 1,1,1,1,1");
+    fn test_unfold_spring_row() {
+        assert_eq!(
+            unfold_spring_row(&parse_spring_row(".# 1")),
+            parse_spring_row(".#?.#?.#?.#?.# 1,1,1,1,1")
+        );
     }
 
     #[ignore]
@@ -530,17 +501,72 @@ mod tests {
         assert_eq!(get_sum_of_num_good_configs_unfolded(SAMPLE_INPUT1), 525152);
     }
 
-    #[ignore]
-    #[test]
-    fn test_asdf() {
-        let mut i = 0 as u64;
-        while i < 40000000000 {
-            if i % 1000000000 == 0 {
-                println!("i: {}", i);
+    fn spring_row_to_str(spring_row: &SpringRow) -> String {
+        let segs = spring_row
+            .working_segs
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        format!("{} {}", spring_row.line_str, segs.join(","))
+    }
+
+    // Shorter rows, fewer or smaller groups, or fewer unknowns. Rows the
+    // groups can't possibly fit in are left out since the brute force
+    // solver can't handle them.
+    fn shrink_spring_row(line: &str) -> Vec<String> {
+        let spring_row = parse_spring_row(line);
+        let mut shrunk = vec![];
+        for idx in 0..spring_row.line_str.len() {
+            let mut line_str = spring_row.line_str.clone();
+            line_str.remove(idx);
+            shrunk.push(SpringRow {
+                line_str,
+                ..spring_row.clone()
+            });
+        }
+        if spring_row.working_segs.len() > 1 {
+            for working_segs in diff::without_each(&spring_row.working_segs) {
+                shrunk.push(SpringRow {
+                    working_segs,
+                    ..spring_row.clone()
+                });
             }
-            i += 1;
         }
-        println!("done");
-        // assert!(false);
+        for idx in 0..spring_row.working_segs.len() {
+            if spring_row.working_segs[idx] > 1 {
+                let mut working_segs = spring_row.working_segs.clone();
+                working_segs[idx] -= 1;
+                shrunk.push(SpringRow {
+                    working_segs,
+                    ..spring_row.clone()
+                });
+            }
+        }
+        for line_str in diff::with_each_char_replaced(&spring_row.line_str, &['.']) {
+            shrunk.push(SpringRow {
+                line_str,
+                ..spring_row.clone()
+            });
+        }
+
+        shrunk
+            .iter()
+            .filter(|spring_row| {
+                let segs = &spring_row.working_segs;
+                segs.iter().sum::<u64>() + segs.len() as u64 - 1 <= spring_row.line_str.len() as u64
+            })
+            .map(spring_row_to_str)
+            .collect()
+    }
+
+    #[test]
+    fn test_get_num_good_configs_differential() {
+        Differ {
+            generate: |seed| crate::generate::generate(seed, 1).trim_end().to_string(),
+            shrink: |line| shrink_spring_row(line),
+            reference: |line| get_num_good_configs(&parse_spring_row(line)),
+            candidate: |line| get_num_good_configs2(&parse_spring_row(line)),
+        }
+        .assert_agree(0..500);
     }
 }
@@ -0,0 +1,72 @@
+use aoc2023_utils::Rng;
+
+// Number of rows.
+pub const DEFAULT_SIZE: usize = 1000;
+
+const MAX_ROW_LEN: usize = 20;
+const MAX_GROUP_LEN: usize = 5;
+
+// Lays out a real row of springs first and then hides some of them, so every
+// row has at least one arrangement.
+fn gen_row(rng: &mut Rng) -> String {
+    let row_len = rng.range(3..MAX_ROW_LEN + 1);
+
+    let mut springs = ".".repeat(rng.range(0..3));
+    let mut groups = vec![];
+    loop {
+        let group_len = rng.range(1..MAX_GROUP_LEN + 1);
+        if springs.len() + group_len > row_len {
+            break;
+        }
+        springs += &"#".repeat(group_len);
+        groups.push(group_len);
+        springs += &".".repeat(rng.range(1..4));
+    }
+    springs.truncate(row_len);
+    springs += &".".repeat(row_len - springs.len());
+    if groups.is_empty() {
+        springs.replace_range(0..1, "#");
+        groups.push(1);
+    }
+
+    let hidden = rng.range(30..80) as f64 / 100.0;
+    let template = springs
+        .chars()
+        .map(|c| if rng.chance(hidden) { '?' } else { c })
+        .collect::<String>();
+    let groups = groups
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    format!("{template} {}", groups.join(","))
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size).map(|_| gen_row(&mut rng) + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_num_good_configs, get_num_good_configs2, unfold_spring_row, Day12};
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let parsed = Day12.parse(&generate(3, 50));
+        assert_eq!(parsed.len(), 50);
+        // Unfolding can always keep the joining '?'s working, so every way the
+        // row works goes with every way each of the other four copies works.
+        for row in &parsed {
+            let num_configs = get_num_good_configs(row);
+            assert!(num_configs > 0);
+            assert!(get_num_good_configs2(&unfold_spring_row(row)) >= num_configs.pow(5));
+        }
+        assert!(Day12.part2(&parsed) >= Day12.part1(&parsed));
+    }
+}
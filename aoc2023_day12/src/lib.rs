@@ -0,0 +1,8 @@
+mod day12;
+pub mod generate;
+
+pub use day12::{
+    get_num_good_configs, get_num_good_configs2, get_sum_of_num_good_configs,
+    get_sum_of_num_good_configs_unfolded, parse_input, parse_spring_row, sum_of_num_good_configs,
+    sum_of_num_good_configs_unfolded, unfold_spring_row, Day12, SpringRow,
+};
@@ -1,5 +1,9 @@
+use aoc2023_utils::{log_debug, par, Solution};
+
+// Everything but `lines` is worked out from it, so it's only ever built from
+// the lines.
 #[derive(Debug, PartialEq, Clone, PartialOrd)]
-struct Pattern {
+pub struct Pattern {
     lines: Vec<String>,
     lines_rev: Vec<String>,
     lines_transposed: Vec<String>,
@@ -7,7 +11,7 @@ struct Pattern {
 }
 
 impl Pattern {
-    pub fn new(
+    fn new(
         lines: Vec<String>,
         lines_rev: Vec<String>,
         lines_transposed: Vec<String>,
@@ -20,9 +24,34 @@ impl Pattern {
             lines_transposed_rev,
         }
     }
+
+    pub fn from_lines(lines: Vec<String>) -> Self {
+        let lines_rev = lines.iter().rev().cloned().collect::<Vec<String>>();
+
+        let lines_transposed = (0..lines[0].len())
+            .map(|i| {
+                lines
+                    .iter()
+                    .map(|line| line.as_bytes()[i] as char)
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        let lines_transposed_rev = lines_transposed
+            .iter()
+            .rev()
+            .cloned()
+            .collect::<Vec<String>>();
+
+        Self::new(lines, lines_rev, lines_transposed, lines_transposed_rev)
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
 }
 
-fn parse_input(s: &str) -> Vec<Vec<String>> {
+pub fn parse_input(s: &str) -> Vec<Vec<String>> {
     let mut patterns = vec![];
 
     let mut pattern = vec![];
@@ -42,33 +71,11 @@ fn parse_input(s: &str) -> Vec<Vec<String>> {
     patterns
 }
 
-fn build_pattern(orig_pattern: &[String]) -> Pattern {
-    let lines = orig_pattern.to_vec();
-    let lines_rev = lines
-        .iter()
-        .rev()
-        .map(|s| s.clone())
-        .collect::<Vec<String>>();
-
-    let lines_transposed = (0..orig_pattern[0].len())
-        .map(|i| {
-            orig_pattern
-                .iter()
-                .map(|line| line.as_bytes()[i] as char)
-                .collect::<String>()
-        })
-        .collect::<Vec<String>>();
-
-    let lines_transposed_rev = lines_transposed
-        .iter()
-        .rev()
-        .map(|s| s.clone())
-        .collect::<Vec<String>>();
-
-    Pattern::new(lines, lines_rev, lines_transposed, lines_transposed_rev)
+pub fn build_pattern(orig_pattern: &[String]) -> Pattern {
+    Pattern::from_lines(orig_pattern.to_vec())
 }
 
-fn build_patterns(orig_patterns: &[Vec<String>]) -> Vec<Pattern> {
+pub fn build_patterns(orig_patterns: &[Vec<String>]) -> Vec<Pattern> {
     orig_patterns
         .iter()
         .map(|orig_pattern| build_pattern(orig_pattern))
@@ -107,7 +114,7 @@ fn find_horizontal_line_of_symmetry(strs: &[String]) -> Option<u32> {
     }
 }
 
-fn get_mirror_val_for_pattern(pattern: &Pattern) -> u32 {
+pub fn get_mirror_val_for_pattern(pattern: &Pattern) -> u32 {
     if let Some(val) = find_horizontal_line_of_symmetry(&pattern.lines) {
         (val + 1) * 100
     } else if let Some(val) = find_horizontal_line_of_symmetry(&pattern.lines_rev) {
@@ -150,13 +157,17 @@ fn get_mirror_val_for_pattern_ignoring(pattern: &Pattern, to_ignore: u32) -> u32
     0
 }
 
-pub fn get_summary_val(s: &str) -> u32 {
-    build_patterns(&parse_input(s))
+pub fn summary_val(parsed_inputs: &[Vec<String>]) -> u32 {
+    build_patterns(parsed_inputs)
         .iter()
-        .map(|pattern| get_mirror_val_for_pattern(&pattern))
+        .map(get_mirror_val_for_pattern)
         .sum()
 }
 
+pub fn get_summary_val(s: &str) -> u32 {
+    summary_val(&parse_input(s))
+}
+
 fn reverse_coord(lines: &[String], row: usize, col: usize) -> Vec<String> {
     lines
         .iter()
@@ -164,42 +175,72 @@ fn reverse_coord(lines: &[String], row: usize, col: usize) -> Vec<String> {
         .map(|(row_idx, line)| {
             let mut bytes = Vec::from(line.as_bytes());
             if row == row_idx {
-                bytes[col] = if bytes[col] == '.' as u8 {
-                    '#' as u8
-                } else {
-                    '.' as u8
-                };
+                bytes[col] = if bytes[col] == b'.' { b'#' } else { b'.' };
             }
             String::from_utf8(bytes).unwrap()
         })
         .collect()
 }
 
-pub fn get_smudged_summary_val(s: &str) -> u32 {
-    let parsed_inputs = parse_input(s);
-
-    let mut sum = 0 as u32;
-
-    for (input_idx, parsed_input) in parsed_inputs.iter().enumerate() {
-        println!("processing input {} (idx {})", input_idx + 1, input_idx);
-        let orig_pattern = build_pattern(&parsed_input);
-        let orig_value = get_mirror_val_for_pattern(&orig_pattern);
-        'outer: for row in 0..parsed_input.len() {
-            for col in 0..parsed_input[0].len() {
-                let smudged_input = reverse_coord(&parsed_input, row, col);
-                let smudged_pattern = build_pattern(&smudged_input);
-                let value = get_mirror_val_for_pattern_ignoring(&smudged_pattern, orig_value);
-                if value != 0 {
-                    sum += value;
-                    break 'outer;
-                }
+fn smudged_val(input_idx: usize, parsed_input: &[String]) -> u32 {
+    log_debug!("processing input {} (idx {})", input_idx + 1, input_idx);
+    let orig_pattern = build_pattern(parsed_input);
+    let orig_value = get_mirror_val_for_pattern(&orig_pattern);
+    for row in 0..parsed_input.len() {
+        for col in 0..parsed_input[0].len() {
+            let smudged_input = reverse_coord(parsed_input, row, col);
+            let smudged_pattern = build_pattern(&smudged_input);
+            let value = get_mirror_val_for_pattern_ignoring(&smudged_pattern, orig_value);
+            if value != 0 {
+                return value;
             }
         }
     }
-    
-    sum
+
+    0
+}
+
+pub fn smudged_summary_val(parsed_inputs: &[Vec<String>]) -> u32 {
+    let indexed_inputs = parsed_inputs.iter().enumerate().collect::<Vec<_>>();
+    par::map(&indexed_inputs, |(input_idx, parsed_input)| {
+        smudged_val(*input_idx, parsed_input)
+    })
+    .iter()
+    .sum()
+}
+
+pub fn get_smudged_summary_val(s: &str) -> u32 {
+    smudged_summary_val(&parse_input(s))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<Vec<String>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        summary_val(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        smudged_summary_val(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed
+            .get(idx)
+            .map(|pattern| format!("{:#?}", build_pattern(pattern)))
+    }
 }
 
+aoc2023_utils::fixture_tests!(Day13);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,14 +358,14 @@ mod tests {
     #[test]
     fn test_build_pattern() {
         assert_eq!(
-            build_pattern(&vec![
+            build_pattern(&[
                 "#.##..##.".to_string(),
                 "..#.##.#.".to_string(),
                 "##......#".to_string(),
                 "##......#".to_string(),
                 "..#.##.#.".to_string(),
                 "..##..##.".to_string(),
-                "#.#.##.#.".to_string(),
+                "#.#.##.#.".to_string()
             ]),
             get_sample_pattern_01a()
         );
@@ -333,19 +374,19 @@ mod tests {
     #[test]
     fn test_find_horizontal_line_of_symmetry() {
         assert_eq!(
-            find_horizontal_line_of_symmetry(&vec![
+            find_horizontal_line_of_symmetry(&[
                 "#.##..##.".to_string(),
                 "..#.##.#.".to_string(),
                 "##......#".to_string(),
                 "##......#".to_string(),
                 "..#.##.#.".to_string(),
                 "..##..##.".to_string(),
-                "#.#.##.#.".to_string(),
+                "#.#.##.#.".to_string()
             ],),
             None
         );
         assert_eq!(
-            find_horizontal_line_of_symmetry(&vec![
+            find_horizontal_line_of_symmetry(&[
                 "#.##..#".to_string(),
                 "..##...".to_string(),
                 "##..###".to_string(),
@@ -354,12 +395,12 @@ mod tests {
                 ".#..#.#".to_string(),
                 "#....#.".to_string(),
                 "##..###".to_string(),
-                "..##...".to_string(),
+                "..##...".to_string()
             ],),
             None
         );
         assert_eq!(
-            find_horizontal_line_of_symmetry(&vec![
+            find_horizontal_line_of_symmetry(&[
                 "..##...".to_string(),
                 "##..###".to_string(),
                 "#....#.".to_string(),
@@ -368,7 +409,7 @@ mod tests {
                 "#....#.".to_string(),
                 "##..###".to_string(),
                 "..##...".to_string(),
-                "#.##..#".to_string(),
+                "#.##..#".to_string()
             ],),
             Some(3)
         );
@@ -403,7 +444,6 @@ mod tests {
         assert_eq!(get_summary_val(SAMPLE_INPUT_1), 405);
 
         let cwd = std::env::current_dir().unwrap();
-        println!("CWD: {:?}", cwd);
 
         let mut infile = "inputs/day13.txt";
         if cwd.ends_with("aoc2023_day13") {
@@ -418,14 +458,14 @@ mod tests {
     fn test_reverse_coord() {
         assert_eq!(
             reverse_coord(
-                &vec![
+                &[
                     "#.##..##.".to_string(),
                     "..#.##.#.".to_string(),
                     "##......#".to_string(),
                     "##......#".to_string(),
                     "..#.##.#.".to_string(),
                     "..##..##.".to_string(),
-                    "#.#.##.#.".to_string(),
+                    "#.#.##.#.".to_string()
                 ],
                 1,
                 2
@@ -442,14 +482,14 @@ mod tests {
         );
         assert_eq!(
             reverse_coord(
-                &vec![
+                &[
                     "#.##..##.".to_string(),
                     "..#.##.#.".to_string(),
                     "##......#".to_string(),
                     "##......#".to_string(),
                     "..#.##.#.".to_string(),
                     "..##..##.".to_string(),
-                    "#.#.##.#.".to_string(),
+                    "#.#.##.#.".to_string()
                 ],
                 1,
                 3
@@ -471,7 +511,6 @@ mod tests {
         assert_eq!(get_smudged_summary_val(SAMPLE_INPUT_1), 400);
 
         let cwd = std::env::current_dir().unwrap();
-        println!("CWD: {:?}", cwd);
 
         let mut infile = "inputs/day13.txt";
         if cwd.ends_with("aoc2023_day13") {
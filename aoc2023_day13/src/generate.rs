@@ -0,0 +1,217 @@
+use aoc2023_utils::Rng;
+
+// Number of patterns.
+pub const DEFAULT_SIZE: usize = 100;
+
+const MIN_DIM: usize = 5;
+const MAX_DIM: usize = 17;
+
+type Pattern = Vec<Vec<bool>>;
+
+fn find(classes: &mut [usize], idx: usize) -> usize {
+    if classes[idx] != idx {
+        classes[idx] = find(classes, classes[idx]);
+    }
+    classes[idx]
+}
+
+// Lines of reflection are given by how many rows (or columns) come before
+// them. Counts the cells that differ from their mirror image.
+fn row_mismatches(pattern: &Pattern, before: usize) -> usize {
+    let span = before.min(pattern.len() - before);
+    (0..span)
+        .map(|i| {
+            let (top, bottom) = (&pattern[before - 1 - i], &pattern[before + i]);
+            top.iter().zip(bottom).filter(|(a, b)| a != b).count()
+        })
+        .sum()
+}
+
+fn transpose(pattern: &Pattern) -> Pattern {
+    (0..pattern[0].len())
+        .map(|col| pattern.iter().map(|row| row[col]).collect())
+        .collect()
+}
+
+// Every line of reflection with its mismatch count, rows first.
+fn all_mismatches(pattern: &Pattern) -> Vec<usize> {
+    let transposed = transpose(pattern);
+    (1..pattern.len())
+        .map(|before| row_mismatches(pattern, before))
+        .chain((1..transposed.len()).map(|before| row_mismatches(&transposed, before)))
+        .collect()
+}
+
+// A pattern that reflects perfectly across a line between rows, and across a
+// line between columns with one smudge. Cells that either reflection ties
+// together get the same value, then one cell in rows the row reflection
+// doesn't reach is flipped to become the smudge.
+fn try_gen_pattern(rng: &mut Rng) -> Option<Pattern> {
+    let num_rows = rng.range(MIN_DIM..MAX_DIM + 1);
+    let num_cols = rng.range(MIN_DIM..MAX_DIM + 1);
+    let row_line = rng.range(1..num_rows);
+    let col_line = rng.range(1..num_cols);
+
+    let row_span = row_line.min(num_rows - row_line);
+    let col_span = col_line.min(num_cols - col_line);
+    let unreflected_rows = (0..num_rows)
+        .filter(|row| row + row_span < row_line || *row >= row_line + row_span)
+        .collect::<Vec<usize>>();
+    if unreflected_rows.is_empty() {
+        return None;
+    }
+
+    let idx = |row: usize, col: usize| row * num_cols + col;
+    let mut classes = (0..num_rows * num_cols).collect::<Vec<usize>>();
+    for row in 0..num_rows {
+        for col in 0..num_cols {
+            if row + row_span >= row_line && row < row_line {
+                let mirror = idx(2 * row_line - 1 - row, col);
+                let (a, b) = (
+                    find(&mut classes, idx(row, col)),
+                    find(&mut classes, mirror),
+                );
+                classes[a] = b;
+            }
+            if col + col_span >= col_line && col < col_line {
+                let mirror = idx(row, 2 * col_line - 1 - col);
+                let (a, b) = (
+                    find(&mut classes, idx(row, col)),
+                    find(&mut classes, mirror),
+                );
+                classes[a] = b;
+            }
+        }
+    }
+
+    let values = (0..num_rows * num_cols)
+        .map(|_| rng.chance(0.5))
+        .collect::<Vec<bool>>();
+    let mut pattern = (0..num_rows)
+        .map(|row| {
+            (0..num_cols)
+                .map(|col| values[find(&mut classes, idx(row, col))])
+                .collect()
+        })
+        .collect::<Pattern>();
+
+    let smudge_row = *rng.choose(&unreflected_rows);
+    let smudge_col = rng.range(col_line - col_span..col_line + col_span);
+    pattern[smudge_row][smudge_col] = !pattern[smudge_row][smudge_col];
+
+    // Random fill can line up into extra reflections, which the puzzle rules
+    // out.
+    let mismatches = all_mismatches(&pattern);
+    let num_perfect = mismatches.iter().filter(|count| **count == 0).count();
+    let num_smudged = mismatches.iter().filter(|count| **count == 1).count();
+    (num_perfect == 1 && num_smudged == 1).then_some(pattern)
+}
+
+fn gen_pattern(rng: &mut Rng) -> Pattern {
+    let pattern = loop {
+        if let Some(pattern) = try_gen_pattern(rng) {
+            break pattern;
+        }
+    };
+    if rng.chance(0.5) {
+        transpose(&pattern)
+    } else {
+        pattern
+    }
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            gen_pattern(&mut rng)
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|rock| if *rock { '#' } else { '.' })
+                        .chain(['\n'])
+                        .collect::<String>()
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day13;
+    use aoc2023_utils::Solution;
+
+    // Summarizes with the brute force mismatch counts rather than the solver.
+    fn summarize(pattern: &Pattern, num_mismatches: usize) -> usize {
+        let mismatches = all_mismatches(pattern);
+        let line = mismatches
+            .iter()
+            .position(|count| *count == num_mismatches)
+            .unwrap();
+        if line < pattern.len() - 1 {
+            (line + 1) * 100
+        } else {
+            line - (pattern.len() - 1) + 1
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 20), generate(1, 20));
+        assert_ne!(generate(1, 20), generate(2, 20));
+
+        let parsed = Day13.parse(&generate(3, 20));
+        assert_eq!(parsed.len(), 20);
+
+        // Every pattern has exactly one perfect line and one smudged line,
+        // and the solver finds the same ones as the brute force.
+        let patterns = parsed
+            .iter()
+            .map(|lines| {
+                lines
+                    .iter()
+                    .map(|line| line.chars().map(|c| c == '#').collect())
+                    .collect()
+            })
+            .collect::<Vec<Pattern>>();
+        for pattern in &patterns {
+            let mismatches = all_mismatches(pattern);
+            assert_eq!(mismatches.iter().filter(|count| **count == 0).count(), 1);
+            assert_eq!(mismatches.iter().filter(|count| **count == 1).count(), 1);
+        }
+        let sum = |num_mismatches| {
+            patterns
+                .iter()
+                .map(|pattern| summarize(pattern, num_mismatches))
+                .sum::<usize>()
+        };
+        assert_eq!(Day13.part1(&parsed) as usize, sum(0));
+        assert_eq!(Day13.part2(&parsed) as usize, sum(1));
+    }
+
+    #[test]
+    fn test_gen_pattern() {
+        let mut rng = Rng::new(4);
+        for _ in 0..50 {
+            let pattern = gen_pattern(&mut rng);
+            let mismatches = all_mismatches(&pattern);
+            assert_eq!(mismatches.iter().filter(|count| **count == 0).count(), 1);
+            assert_eq!(mismatches.iter().filter(|count| **count == 1).count(), 1);
+            assert_ne!(summarize(&pattern, 0), summarize(&pattern, 1));
+        }
+    }
+
+    #[test]
+    fn test_all_mismatches() {
+        let pattern = vec![
+            vec![true, false, false, true],
+            vec![false, false, false, false],
+            vec![false, false, true, false],
+        ];
+        // Rows 1|2, 2|3, then columns 1|2, 2|3, 3|4.
+        assert_eq!(all_mismatches(&pattern), vec![2, 1, 1, 1, 2]);
+    }
+}
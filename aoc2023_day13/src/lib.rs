@@ -0,0 +1,7 @@
+mod day13;
+pub mod generate;
+
+pub use day13::{
+    build_pattern, build_patterns, get_mirror_val_for_pattern, get_smudged_summary_val,
+    get_summary_val, parse_input, smudged_summary_val, summary_val, Day13, Pattern,
+};
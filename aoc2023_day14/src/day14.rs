@@ -1,28 +1,32 @@
-use std::collections::HashMap;
+use aoc2023_utils::frames::{Frame, Recorder};
+use aoc2023_utils::{cycle, log_debug, Grid, Image, Rgb, Solution};
 
-type Board = Vec<Vec<u8>>;
+pub type Board = Grid<u8>;
+
+const ROUND_ROCK_COLOR: Rgb = [230, 230, 230];
+const CUBE_ROCK_COLOR: Rgb = [150, 90, 40];
+const EMPTY_COLOR: Rgb = [30, 30, 30];
 
 #[allow(dead_code)]
 fn print_board(board: &Board) {
-    board.iter().for_each(|line| {
-        let line_str = String::from_utf8(line.clone()).unwrap();
-        println!("{line_str}");
-    });
+    log_debug!("board:\n{}", board_to_str(board));
 }
 
-fn parse_input(s: &str) -> Board {
-    s.lines().map(|line| line.as_bytes().to_vec()).collect()
+pub fn parse_input(s: &str) -> Board {
+    Grid::parse(s, |c| match c {
+        'O' | '#' | '.' => c as u8,
+        _ => panic!("unexpected {c:?} on the platform"),
+    })
 }
 
-fn get_summary(board: &Board) -> u32 {
+pub fn get_summary(board: &Board) -> u32 {
     let mut load = 0;
-    let num_rows = board.len();
-    let num_cols = board[0].len();
-    for col in 0..num_cols {
+    let num_rows = board.num_rows();
+    for col in board.cols() {
         let mut load_base = num_rows;
         let mut num_stones = 0;
-        for row in 0..num_rows {
-            let c = board[row][col] as char;
+        for (row, c) in col.enumerate() {
+            let c = *c as char;
             if c == '#' {
                 for stone in 0..num_stones {
                     load += load_base - stone;
@@ -45,38 +49,31 @@ pub fn get_summary_from_input(input: &str) -> u32 {
     get_summary(&parse_input(input))
 }
 
-fn get_load(board: &Board) -> u32 {
+pub fn get_load(board: &Board) -> u32 {
     let mut load = 0;
-    let num_rows = board.len();
-    let num_cols = board[0].len();
-    for col in 0..num_cols {
-        for row in 0..num_rows {
-            let c = board[row][col] as char;
-            if c == 'O' {
-                load += num_rows - row;
-            }
-        }
+    let num_rows = board.num_rows();
+    for (row, line) in board.rows().enumerate() {
+        let num_stones = line.iter().filter(|c| **c == b'O').count();
+        load += num_stones * (num_rows - row);
     }
 
     load as u32
 }
 
-fn roll_to_top(board: &mut Board) {
-    let num_rows = board.len();
-    let num_cols = board[0].len();
-    for col in 0..num_cols {
+pub fn roll_to_top(board: &mut Board) {
+    for col in 0..board.num_cols() {
         let mut start_row = 0;
         let mut num_stones = 0;
-        for row in 0..num_rows {
-            let c = board[row][col] as char;
+        for row in 0..board.num_rows() {
+            let c = board[(row, col)] as char;
             if c == '#' {
                 start_row = row + 1;
                 num_stones = 0;
             } else if c == 'O' {
                 let target_row = start_row + num_stones;
                 if target_row != row {
-                    board[target_row][col] = 'O' as u8;
-                    board[row][col] = '.' as u8;
+                    board[(target_row, col)] = b'O';
+                    board[(row, col)] = b'.';
                 }
                 num_stones += 1;
             }
@@ -85,88 +82,141 @@ fn roll_to_top(board: &mut Board) {
 }
 
 fn rotate_board_ccw(board: &Board) -> Board {
-    let num_rows = board.len();
-    let num_cols = board[0].len();
-
-    let mut new_board = (0..num_cols)
-        .map(|_| {
-            std::iter::repeat(' ' as u8)
-                .take(num_rows)
-                .collect::<Vec<u8>>()
-        })
-        .collect::<Board>();
-
-    for row in 0..num_rows {
-        for col in 0..num_cols {
-            let new_row = col;
-            let new_col = num_rows - row - 1;
-            new_board[new_row][new_col] = board[row][col];
-        }
+    let num_rows = board.num_rows();
+    let mut new_board = Grid::new(board.num_cols(), num_rows, b' ');
+
+    for ((row, col), c) in board.iter() {
+        let new_row = col;
+        let new_col = num_rows - row - 1;
+        new_board[(new_row, new_col)] = *c;
     }
 
     new_board
 }
 
-fn run_cycle(board: &Board) -> Board {
+// `on_tilt` sees the board after each of the four tilts, along with how many
+// times it's been turned counterclockwise so far.
+fn run_cycle_with(board: &Board, mut on_tilt: impl FnMut(&Board, usize)) -> Board {
     let mut board = board.clone();
-    (0..4).for_each(|_| {
+    (0..4).for_each(|num_turns| {
         roll_to_top(&mut board);
+        on_tilt(&board, num_turns);
         board = rotate_board_ccw(&board);
     });
     board
 }
 
-fn board_to_str(board: &Board) -> String {
-    let row_strs = board
-        .iter()
-        .map(|line| String::from_utf8(line.clone()).unwrap())
-        .collect::<Vec<String>>();
+pub fn run_cycle(board: &Board) -> Board {
+    run_cycle_with(board, |_, _| {})
+}
 
-    row_strs.join("\n")
+pub fn board_to_str(board: &Board) -> String {
+    board.to_string_with(|c| *c as char)
 }
 
-fn run_n_cycles(board: &Board, num_cycles: u32) -> Board {
-    let mut history = HashMap::<String, u32>::new();
+pub fn render(board: &Board) -> Image {
+    Image::from_grid(board, |space| match space {
+        b'O' => ROUND_ROCK_COLOR,
+        b'#' => CUBE_ROCK_COLOR,
+        _ => EMPTY_COLOR,
+    })
+}
 
-    let mut board = board.clone();
-    let mut cycles_to_go = num_cycles;
-    let mut cyclic_length = 0;
-
-    let hash_str = board_to_str(&board);
-    history.insert(hash_str, 0);
-
-    for i in 0..num_cycles {
-        board = run_cycle(&board);
-        let hash_str = board_to_str(&board);
-        cycles_to_go -= 1;
-        if let Some(prev_cycle) = history.insert(hash_str, i + 1) {
-            cyclic_length = (i + 1) - prev_cycle;
-            println!("Found a repeat on idx {i} of cycle {prev_cycle} for a cyclic length of {cyclic_length}");
-            break;
-        }
-    };
+struct BoardFrame<'a>(&'a Board);
 
-    if cycles_to_go > 0 {
-        let old_cycles_to_go = cycles_to_go;
-        cycles_to_go = cycles_to_go % cyclic_length;
-        println!("Skipping from {old_cycles_to_go} cycles to go down to {cycles_to_go} cycles to go");
+impl Frame for BoardFrame<'_> {
+    fn to_text(&self) -> String {
+        board_to_str(self.0)
     }
 
-    (0..cycles_to_go).for_each(|_| {
-        board = run_cycle(&board);
-    });
+    fn to_image(&self) -> Image {
+        render(self.0)
+    }
+}
 
-    board
+// A frame for the starting board and then one per tilt, all turned back to
+// the way the board started out.
+pub fn record_cycles(board: &Board, num_cycles: u32, recorder: &mut Recorder) {
+    recorder.record(&BoardFrame(board));
+    let mut board = board.clone();
+    for _ in 0..num_cycles {
+        board = run_cycle_with(&board, |tilted, num_turns| {
+            let upright =
+                (0..(4 - num_turns) % 4).fold(tilted.clone(), |board, _| rotate_board_ccw(&board));
+            recorder.record(&BoardFrame(&upright));
+        });
+    }
+}
+
+// The board repeats itself long before a billion cycles, so skip around the
+// loop instead of running every one.
+pub fn run_n_cycles(board: &Board, num_cycles: u64) -> Board {
+    cycle::nth(board, run_cycle, num_cycles)
 }
 
-pub fn get_cycled_summary_from_input(input: &str, num_cycles: u32) -> u32 {
+pub fn get_cycled_summary_from_input(input: &str, num_cycles: u64) -> u32 {
     let board = run_n_cycles(&parse_input(input), num_cycles);
     get_load(&board)
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Board;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const PARAMS: &'static [&'static str] = &["num_cycles"];
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        get_summary(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        get_load(&run_n_cycles(parsed, 1000000000))
+    }
+
+    // The platform tilted north, as part 1 leaves it.
+    fn render(&self, parsed: &Self::Parsed) -> Option<Image> {
+        let mut board = parsed.clone();
+        roll_to_top(&mut board);
+        Some(render(&board))
+    }
+
+    // `start` is the number of spin cycles, 1 if it's not given.
+    fn record(
+        &self,
+        parsed: &Self::Parsed,
+        start: Option<&str>,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        let num_cycles = match start {
+            Some(start) => start
+                .parse::<u32>()
+                .map_err(|_| format!("bad number of cycles: {start}"))?,
+            None => 1,
+        };
+        record_cycles(parsed, num_cycles, recorder);
+        Ok(())
+    }
+
+    fn solve_with_param(&self, parsed: &Self::Parsed, _name: &str, value: u32) -> String {
+        get_load(&run_n_cycles(parsed, value.into())).to_string()
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day14);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc2023_utils::diff::{self, Differ};
+    use aoc2023_utils::frames::FrameFormat;
+    use std::fs;
 
     const SAMPLE_INPUT_1: &str = concat!(
         "O....#....\n",
@@ -207,6 +257,13 @@ mod tests {
         "...O#.O.#.\n",
     );
 
+    #[test]
+    #[should_panic(expected = "unexpected 'ŏ'")]
+    fn test_parse_input_rejects_non_ascii() {
+        // U+014F would have come out as b'O' cut down to a byte.
+        parse_input("O.\nŏ#\n");
+    }
+
     #[test]
     fn test_get_summary() {
         assert_eq!(get_summary(&parse_input(SAMPLE_INPUT_1)), 136);
@@ -226,6 +283,31 @@ mod tests {
         assert_eq!(board, parse_input(SAMPLE_INPUT_1_ROLLED));
     }
 
+    #[test]
+    fn test_render() {
+        let image = render(&parse_input(SAMPLE_INPUT_1));
+        assert_eq!((image.width(), image.height()), (10, 10));
+        assert_eq!(image.get(0, 0), ROUND_ROCK_COLOR);
+        assert_eq!(image.get(1, 0), EMPTY_COLOR);
+        assert_eq!(image.get(5, 0), CUBE_ROCK_COLOR);
+    }
+
+    #[test]
+    fn test_record_cycles() {
+        let dir = std::env::temp_dir().join(format!("aoc_day14_frames_{}", std::process::id()));
+        let board = parse_input(SAMPLE_INPUT_1);
+        let mut recorder = Recorder::to_files(&dir, FrameFormat::Text, 1);
+        record_cycles(&board, 2, &mut recorder);
+        assert_eq!(recorder.finish().unwrap(), 9);
+
+        let frame = |n: usize| fs::read_to_string(dir.join(format!("frame-{n:05}.txt"))).unwrap();
+        assert_eq!(frame(1), board_to_str(&board));
+        assert_eq!(frame(2), board_to_str(&parse_input(SAMPLE_INPUT_1_ROLLED)));
+        assert_eq!(frame(5), board_to_str(&run_cycle(&board)));
+        assert_eq!(frame(9), board_to_str(&run_n_cycles(&board, 2)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_rotate_board_ccw() {
         let board = parse_input(SAMPLE_INPUT_1);
@@ -237,10 +319,55 @@ mod tests {
 
     #[test]
     fn test_run_n_cycles() {
-        println!();
         let board = parse_input(SAMPLE_INPUT_1);
         let board = run_n_cycles(&board, 1000000000);
         let load = get_load(&board);
         assert_eq!(load, 64);
     }
+
+    // Boards missing a row or column, or with one rock cleared away.
+    fn shrink_board(input: &str) -> Vec<String> {
+        let lines = input.lines().collect::<Vec<&str>>();
+        let mut shrunk = vec![];
+        if lines.len() > 1 {
+            shrunk.extend(
+                diff::without_each(&lines)
+                    .iter()
+                    .map(|lines| lines.join("\n")),
+            );
+        }
+        if lines[0].len() > 1 {
+            for col in 0..lines[0].len() {
+                let lines = lines
+                    .iter()
+                    .map(|line| [&line[..col], &line[col + 1..]].concat())
+                    .collect::<Vec<String>>();
+                shrunk.push(lines.join("\n"));
+            }
+        }
+        for (row, line) in lines.iter().enumerate() {
+            for cleared in diff::with_each_char_replaced(line, &['.']) {
+                let mut lines = lines.clone();
+                lines[row] = &cleared;
+                shrunk.push(lines.join("\n"));
+            }
+        }
+
+        shrunk.into_iter().map(|board| board + "\n").collect()
+    }
+
+    #[test]
+    fn test_get_summary_differential() {
+        Differ {
+            generate: |seed| crate::generate::generate(seed, 1 + seed as usize % 12),
+            shrink: |input| shrink_board(input),
+            reference: |input| get_summary(&parse_input(input)),
+            candidate: |input| {
+                let mut board = parse_input(input);
+                roll_to_top(&mut board);
+                get_load(&board)
+            },
+        }
+        .assert_agree(0..200);
+    }
 }
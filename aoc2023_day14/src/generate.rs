@@ -0,0 +1,61 @@
+use aoc2023_utils::Rng;
+
+// Width and height of the platform.
+pub const DEFAULT_SIZE: usize = 100;
+
+const ROUND_ROCK_CHANCE: f64 = 0.2;
+const CUBE_ROCK_CHANCE: f64 = 0.1;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    if rng.chance(ROUND_ROCK_CHANCE) {
+                        'O'
+                    } else if rng.chance(CUBE_ROCK_CHANCE) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day14;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 20), generate(1, 20));
+        assert_ne!(generate(1, 20), generate(2, 20));
+
+        let input = generate(3, 20);
+        let parsed = Day14.parse(&input);
+        assert_eq!(parsed.bounds(), (20, 20));
+        assert!(input.contains('O') && input.contains('#'));
+
+        // Tilting north never lowers the load, and no load beats every rock
+        // sitting on the north edge.
+        let rocks_per_row = input
+            .lines()
+            .map(|line| line.matches('O').count() as u32)
+            .collect::<Vec<u32>>();
+        let num_rocks = rocks_per_row.iter().sum::<u32>();
+        let untilted_load = (1..=20)
+            .rev()
+            .zip(&rocks_per_row)
+            .map(|(load, num)| load * num)
+            .sum();
+        assert!(Day14.part1(&parsed) >= untilted_load);
+        assert!(Day14.part1(&parsed) <= num_rocks * 20);
+        assert!((1..=num_rocks * 20).contains(&Day14.part2(&parsed)));
+    }
+}
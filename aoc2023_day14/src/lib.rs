@@ -0,0 +1,7 @@
+mod day14;
+pub mod generate;
+
+pub use day14::{
+    board_to_str, get_cycled_summary_from_input, get_load, get_summary, get_summary_from_input,
+    parse_input, record_cycles, render, roll_to_top, run_cycle, run_n_cycles, Board, Day14,
+};
@@ -1,5 +1,7 @@
-fn hash(s: &str) -> u8 {
-    let mut hash = 0 as u32;
+use aoc2023_utils::Solution;
+
+pub fn hash(s: &str) -> u8 {
+    let mut hash = 0_u32;
     s.as_bytes().iter().for_each(|c| {
         hash += *c as u32;
         hash *= 17;
@@ -9,22 +11,31 @@ fn hash(s: &str) -> u8 {
     hash as u8
 }
 
+pub fn parse_input(s: &str) -> Vec<String> {
+    s.trim().split(',').map(|step| step.to_string()).collect()
+}
+
+pub fn sum_step_hashes(steps: &[String]) -> u32 {
+    steps.iter().map(|step| hash(step) as u32).sum()
+}
+
 pub fn parse_and_sum_step_hashes(s: &str) -> u32 {
-    s.trim().split(',').map(|part| hash(part) as u32).sum()
+    sum_step_hashes(&parse_input(s))
 }
 
 #[derive(Debug, PartialEq, Clone, PartialOrd)]
-struct Slot {
-    label: String,
-    lens: u32,
+pub struct Slot {
+    pub label: String,
+    pub lens: u32,
 }
 
-enum Instr {
+#[derive(Debug, PartialEq)]
+pub enum Instr {
     Insert(String, u32),
     Remove(String),
 }
 
-fn parse_instr(s: &str) -> Instr {
+pub fn parse_instr(s: &str) -> Instr {
     if let Some(idx) = s.find('=') {
         let label = s[0..idx].to_string();
         let lens = s[idx + 1..].parse().unwrap();
@@ -54,7 +65,7 @@ fn do_delete(label: &str, slots: &mut Vec<Slot>) {
     }
 }
 
-fn do_instr(instr: &Instr, boxes: &mut Vec<Vec<Slot>>) {
+fn do_instr(instr: &Instr, boxes: &mut [Vec<Slot>]) {
     match instr {
         Instr::Insert(label, lens) => {
             let hash = hash(label);
@@ -67,16 +78,14 @@ fn do_instr(instr: &Instr, boxes: &mut Vec<Vec<Slot>>) {
     }
 }
 
-pub fn process_input(s: &str) -> u64 {
-    let instructions = s.trim().split(',');
-
+pub fn get_focus_power(steps: &[String]) -> u64 {
     let mut boxes = vec![Vec::<Slot>::new(); 256];
 
-    instructions.for_each(|instr| {
+    steps.iter().for_each(|instr| {
         do_instr(&parse_instr(instr), &mut boxes);
     });
 
-    let mut focus_power = 0 as u64;
+    let mut focus_power = 0_u64;
     boxes.iter().enumerate().for_each(|(box_idx, the_box)| {
         the_box.iter().enumerate().for_each(|(slot_idx, slot)| {
             let box_num = (box_idx + 1) as u64;
@@ -89,6 +98,38 @@ pub fn process_input(s: &str) -> u64 {
     focus_power
 }
 
+pub fn process_input(s: &str) -> u64 {
+    get_focus_power(&parse_input(s))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        sum_step_hashes(parsed)
+    }
+
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        get_focus_power(parsed)
+    }
+
+    fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+        parsed
+            .get(idx)
+            .map(|step| format!("{:?}", parse_instr(step)))
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day15);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,69 @@
+use aoc2023_utils::Rng;
+
+// Number of steps.
+pub const DEFAULT_SIZE: usize = 4000;
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const REMOVE_CHANCE: f64 = 0.3;
+
+fn gen_label(rng: &mut Rng) -> String {
+    (0..rng.range(2..7))
+        .map(|_| *rng.choose(LETTERS) as char)
+        .collect()
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    // Draw from a limited set of labels so steps keep hitting the same lenses.
+    let labels = (0..(size / 4).max(1))
+        .map(|_| gen_label(&mut rng))
+        .collect::<Vec<String>>();
+
+    let steps = (0..size)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(REMOVE_CHANCE) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.range(1..10))
+            }
+        })
+        .collect::<Vec<String>>();
+
+    steps.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day15;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 50), generate(1, 50));
+        assert_ne!(generate(1, 50), generate(2, 50));
+
+        let input = generate(3, 50);
+        let parsed = Day15.parse(&input);
+        assert_eq!(parsed.len(), 50);
+        let mut removals = vec![];
+        for step in &parsed {
+            let (label, op) = step.split_at(step.find(['-', '=']).unwrap());
+            assert!((2..=6).contains(&label.len()), "{step}");
+            assert!(label.bytes().all(|c| LETTERS.contains(&c)), "{step}");
+            assert!(
+                op == "-" || (op.len() == 2 && op.starts_with('=')),
+                "{step}"
+            );
+            removals.push(format!("{label}-"));
+        }
+        assert!(Day15.part1(&parsed) > 0);
+        assert!(Day15.part2(&parsed) > 0);
+
+        // Taking every lens back out leaves nothing to focus.
+        let cleared = Day15.parse(&format!("{},{}", input.trim_end(), removals.join(",")));
+        assert_eq!(Day15.part2(&cleared), 0);
+    }
+}
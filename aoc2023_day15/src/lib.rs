@@ -0,0 +1,7 @@
+mod day15;
+pub mod generate;
+
+pub use day15::{
+    get_focus_power, hash, parse_and_sum_step_hashes, parse_input, parse_instr, process_input,
+    sum_step_hashes, Day15, Instr, Slot,
+};
@@ -1,49 +1,27 @@
+use aoc2023_utils::frames::{Frame, Recorder};
+use aoc2023_utils::{log_debug, par, Coord, Direction, Grid, Image, Rgb, Solution};
+
 #[derive(Debug, PartialEq, Clone, PartialOrd, Default)]
 struct Space {
     seen_dirs: [bool; 4],
 }
 
-type Board = Vec<Vec<Space>>;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Coord {
-    row: usize,
-    col: usize,
-}
+type Board = Grid<Space>;
+pub type Optics = Grid<char>;
 
-impl Coord {
-    pub fn new(row: usize, col: usize) -> Self {
-        Self { row, col }
-    }
-}
+const ENERGIZED_COLOR: Rgb = [255, 220, 60];
+const OPTIC_COLOR: Rgb = [120, 120, 140];
+const EMPTY_COLOR: Rgb = [20, 20, 30];
+const BEAM_COLOR: Rgb = [255, 60, 60];
 
-#[repr(u8)]
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Dir {
-    Up = 0,
-    Right = 1,
-    Down = 2,
-    Left = 3,
-}
-
-impl Dir {
-    pub fn is_horizontal(&self) -> bool {
-        *self == Dir::Left || *self == Dir::Right
-    }
-
-    pub fn is_vertical(&self) -> bool {
-        *self == Dir::Up || *self == Dir::Down
-    }
-}
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Beam {
-    coord: Coord,
-    dir: Dir,
+pub struct Beam {
+    pub coord: Coord,
+    pub dir: Direction,
 }
 
 impl Beam {
-    pub fn new(row: usize, col: usize, dir: Dir) -> Self {
+    pub fn new(row: usize, col: usize, dir: Direction) -> Self {
         Self {
             coord: Coord::new(row, col),
             dir,
@@ -51,122 +29,64 @@ impl Beam {
     }
 }
 
-fn parse_input(s: &str) -> Vec<String> {
-    s.lines().map(|line| line.to_string()).collect()
+pub fn parse_input(s: &str) -> Optics {
+    Grid::parse(s, |c| c)
 }
 
 fn create_board(rows: usize, cols: usize) -> Board {
-    vec![vec![Space::default(); cols]; rows]
-}
-
-fn move_beam_up(beam: &Beam) -> Option<Beam> {
-    let row = beam.coord.row;
-    let col = beam.coord.col;
-    if row > 0 {
-        Some(Beam::new(row - 1, col, Dir::Up))
-    } else {
-        None
-    }
-}
-
-fn move_beam_down(beam: &Beam, max_rows: usize) -> Option<Beam> {
-    let row = beam.coord.row;
-    let col = beam.coord.col;
-    if row < (max_rows - 1) {
-        Some(Beam::new(row + 1, col, Dir::Down))
-    } else {
-        None
-    }
-}
-
-fn move_beam_left(beam: &Beam) -> Option<Beam> {
-    let row = beam.coord.row;
-    let col = beam.coord.col;
-    if col > 0 {
-        Some(Beam::new(row, col - 1, Dir::Left))
-    } else {
-        None
-    }
+    Grid::new(rows, cols, Space::default())
 }
 
-fn move_beam_right(beam: &Beam, max_cols: usize) -> Option<Beam> {
-    let row = beam.coord.row;
-    let col = beam.coord.col;
-    if col < (max_cols - 1) {
-        Some(Beam::new(row, col + 1, Dir::Right))
-    } else {
-        None
-    }
+fn move_beam(beam: &Beam, dir: Direction, bounds: (usize, usize)) -> Option<Beam> {
+    beam.coord
+        .step(dir, bounds)
+        .map(|coord| Beam { coord, dir })
 }
 
-fn step_beam(beam: &Beam, optics: &Vec<String>) -> (Option<Beam>, Option<Beam>) {
-    let row = beam.coord.row;
-    let col = beam.coord.col;
-    let max_rows = optics.len();
-    let max_cols = optics[0].len();
-    let optic = optics[row].chars().nth(col).unwrap();
-    if beam.dir.is_horizontal() && (optic == '|') {
-        let new_beam1 = move_beam_up(beam);
-        let new_beam2 = move_beam_down(beam, max_rows);
-        return (new_beam1, new_beam2);
-    }
-
-    if beam.dir.is_vertical() && (optic == '-') {
-        let new_beam1 = move_beam_left(beam);
-        let new_beam2 = move_beam_right(beam, max_cols);
+fn step_beam(beam: &Beam, optics: &Optics) -> (Option<Beam>, Option<Beam>) {
+    let bounds = optics.bounds();
+    let optic = optics[beam.coord];
+    let split = match optic {
+        '|' => beam.dir.is_horizontal(),
+        '-' => beam.dir.is_vertical(),
+        _ => false,
+    };
+    if split {
+        let new_beam1 = move_beam(beam, beam.dir.turn_left(), bounds);
+        let new_beam2 = move_beam(beam, beam.dir.turn_right(), bounds);
         return (new_beam1, new_beam2);
     }
 
-    let go_up =
-        (beam.dir == Dir::Left && optic == '\\') || (beam.dir == Dir::Right && optic == '/');
-    if go_up {
-        return (move_beam_up(beam), None);
-    }
-
-    let go_down =
-        (beam.dir == Dir::Left && optic == '/') || (beam.dir == Dir::Right && optic == '\\');
-    if go_down {
-        return (move_beam_down(beam, max_rows), None);
-    }
-
-    let go_left = (beam.dir == Dir::Up && optic == '\\') || (beam.dir == Dir::Down && optic == '/');
-    if go_left {
-        return (move_beam_left(beam), None);
-    }
+    let new_dir = match optic {
+        '/' if beam.dir.is_horizontal() => beam.dir.turn_left(),
+        '/' => beam.dir.turn_right(),
+        '\\' if beam.dir.is_horizontal() => beam.dir.turn_right(),
+        '\\' => beam.dir.turn_left(),
+        _ => beam.dir,
+    };
 
-    let go_right =
-        (beam.dir == Dir::Up && optic == '/') || (beam.dir == Dir::Down && optic == '\\');
-    if go_right {
-        return (move_beam_right(beam, max_cols), None);
-    }
-
-    match beam.dir {
-        Dir::Up => (move_beam_up(beam), None),
-        Dir::Down => (move_beam_down(beam, max_rows), None),
-        Dir::Left => (move_beam_left(beam), None),
-        Dir::Right => (move_beam_right(beam, max_cols), None),
-    }
+    (move_beam(beam, new_dir, bounds), None)
 }
 
 fn filter_beams(board: &mut Board, beams: &[Beam]) -> Vec<Beam> {
     beams
         .iter()
         .filter(|beam| {
-            let space = &board[beam.coord.row][beam.coord.col];
+            let space = &board[beam.coord];
             !space.seen_dirs[beam.dir as usize]
         })
-        .map(|beam| beam.clone())
+        .copied()
         .collect()
 }
 
 fn update_board(board: &mut Board, beams: &[Beam]) {
     beams.iter().for_each(|beam| {
-        let space = &mut board[beam.coord.row][beam.coord.col];
+        let space = &mut board[beam.coord];
         space.seen_dirs[beam.dir as usize] = true;
     });
 }
 
-fn step(board: &mut Board, beams: &[Beam], optics: &Vec<String>) -> Vec<Beam> {
+fn step(board: &mut Board, beams: &[Beam], optics: &Optics) -> Vec<Beam> {
     let mut new_beams = vec![];
 
     beams.iter().for_each(|beam| {
@@ -192,78 +112,194 @@ fn space_is_energized(space: &Space) -> bool {
 fn count_energized(board: &Board) -> u32 {
     board
         .iter()
-        .map(|row| row.iter().filter(|space| space_is_energized(space)).count() as u32)
-        .sum()
+        .filter(|(_, space)| space_is_energized(space))
+        .count() as u32
 }
 
 fn board_to_energized_str(board: &Board) -> String {
-    let row_strs = board
-        .iter()
-        .map(|row| {
-            row.iter()
-                .map(|space| if space_is_energized(space) { '#' } else { '.' })
-                .collect::<String>()
-        })
-        .collect::<Vec<String>>();
-
-    row_strs.join("\n")
+    board.to_string_with(|space| if space_is_energized(space) { '#' } else { '.' })
 }
 
 #[allow(dead_code)]
 fn print_board_energized(board: &Board) {
-    println!("\n{}", board_to_energized_str(board));
+    log_debug!("energized:\n{}", board_to_energized_str(board));
 }
 
-fn get_num_energized(optics: &Vec<String>, first_beam: &Beam) -> u32 {
-    let mut board = create_board(optics.len(), optics[0].len());
-    let mut beams = vec![first_beam.clone()];
+// `on_step` sees the board after each generation of beams.
+fn energize(optics: &Optics, first_beam: &Beam, mut on_step: impl FnMut(&Board, &[Beam])) -> Board {
+    let mut board = create_board(optics.num_rows(), optics.num_cols());
+    let mut beams = vec![*first_beam];
 
     update_board(&mut board, &beams);
     // print_board_energized(&board);
+    on_step(&board, &beams);
 
     while !beams.is_empty() {
-        beams = step(&mut board, &beams, &optics);
+        beams = step(&mut board, &beams, optics);
         // print_board_energized(&board);
+        on_step(&board, &beams);
     }
 
-    count_energized(&board)
+    board
+}
+
+// Energized tiles in front of the optics, which show through where the beam
+// hasn't been, and the heads of the beams in front of everything.
+struct EnergizedFrame<'a> {
+    optics: &'a Optics,
+    board: &'a Board,
+    beams: &'a [Beam],
+}
+
+impl Frame for EnergizedFrame<'_> {
+    fn to_text(&self) -> String {
+        let mut tiles = self.optics.clone();
+        self.board
+            .iter()
+            .filter(|(coord, space)| space_is_energized(space) && self.optics[*coord] == '.')
+            .for_each(|(coord, _)| tiles[coord] = '#');
+        self.beams.iter().for_each(|beam| tiles[beam.coord] = '@');
+        tiles.to_string_with(|tile| *tile)
+    }
+
+    fn to_image(&self) -> Image {
+        let mut image = Image::from_grid(self.optics, |optic| match optic {
+            '.' => EMPTY_COLOR,
+            _ => OPTIC_COLOR,
+        });
+        self.board
+            .iter()
+            .filter(|(_, space)| space_is_energized(space))
+            .for_each(|((row, col), _)| image.set(col, row, ENERGIZED_COLOR));
+        self.beams
+            .iter()
+            .for_each(|beam| image.set(beam.coord.col, beam.coord.row, BEAM_COLOR));
+        image
+    }
+}
+
+pub fn get_num_energized(optics: &Optics, first_beam: &Beam) -> u32 {
+    count_energized(&energize(optics, first_beam, |_, _| {}))
+}
+
+pub fn render(optics: &Optics, first_beam: &Beam) -> Image {
+    let board = energize(optics, first_beam, |_, _| {});
+    EnergizedFrame {
+        optics,
+        board: &board,
+        beams: &[],
+    }
+    .to_image()
+}
+
+pub fn record(optics: &Optics, first_beam: &Beam, recorder: &mut Recorder) {
+    energize(optics, first_beam, |board, beams| {
+        recorder.record(&EnergizedFrame {
+            optics,
+            board,
+            beams,
+        })
+    });
+}
+
+// "row,col,dir", e.g. "0,3,down".
+pub fn parse_beam(s: &str, optics: &Optics) -> Result<Beam, String> {
+    let bad_beam = || format!("bad beam: {s}, expected row,col,up|right|down|left");
+    let [row, col, dir] = s.split(',').collect::<Vec<&str>>()[..] else {
+        return Err(bad_beam());
+    };
+    let row = row.trim().parse::<usize>().map_err(|_| bad_beam())?;
+    let col = col.trim().parse::<usize>().map_err(|_| bad_beam())?;
+    let dir = match dir.trim() {
+        "up" => Direction::Up,
+        "right" => Direction::Right,
+        "down" => Direction::Down,
+        "left" => Direction::Left,
+        _ => return Err(bad_beam()),
+    };
+    if !optics.in_bounds(row, col) {
+        return Err(format!("beam starts off the grid: {s}"));
+    }
+
+    Ok(Beam::new(row, col, dir))
 }
 
 pub fn get_num_energized_from_input(s: &str) -> u32 {
     let optics = parse_input(s);
-    get_num_energized(&optics, &Beam::new(0, 0, Dir::Right))
+    get_num_energized(&optics, &Beam::new(0, 0, Direction::Right))
+}
+
+pub fn get_max_num_energized(optics: &Optics) -> u32 {
+    let num_rows = optics.num_rows();
+    let num_cols = optics.num_cols();
+
+    let mut first_beams = vec![];
+    (0..num_cols).for_each(|col| {
+        first_beams.push(Beam::new(0, col, Direction::Down));
+        first_beams.push(Beam::new(num_rows - 1, col, Direction::Up));
+    });
+    (0..num_rows).for_each(|row| {
+        first_beams.push(Beam::new(row, 0, Direction::Right));
+        first_beams.push(Beam::new(row, num_cols - 1, Direction::Left));
+    });
+
+    par::map(&first_beams, |first_beam| {
+        get_num_energized(optics, first_beam)
+    })
+    .into_iter()
+    .max()
+    .unwrap_or(0)
 }
 
 pub fn get_max_num_energized_from_input(s: &str) -> u32 {
-    let optics = parse_input(s);
+    get_max_num_energized(&parse_input(s))
+}
 
-    let num_rows = optics.len();
-    let num_cols = optics[0].len();
+pub struct Day16;
 
-    let mut max_energized = 0 as u32;
+impl Solution for Day16 {
+    type Parsed = Optics;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    (0..num_cols).for_each(|col| {
-        let energized = get_num_energized(&optics, &Beam::new(0, col, Dir::Down));
-        max_energized = max_energized.max(energized);
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
 
-        let energized = get_num_energized(&optics, &Beam::new(num_rows - 1, col, Dir::Up));
-        max_energized = max_energized.max(energized);
-    });
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        get_num_energized(parsed, &Beam::new(0, 0, Direction::Right))
+    }
 
-    (0..num_rows).for_each(|row| {
-        let energized = get_num_energized(&optics, &Beam::new(row, 0, Dir::Right));
-        max_energized = max_energized.max(energized);
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+        get_max_num_energized(parsed)
+    }
 
-        let energized = get_num_energized(&optics, &Beam::new(row, num_cols - 1, Dir::Left));
-        max_energized = max_energized.max(energized);
-    });
+    fn render(&self, parsed: &Self::Parsed) -> Option<Image> {
+        Some(render(parsed, &Beam::new(0, 0, Direction::Right)))
+    }
 
-    max_energized
+    fn record(
+        &self,
+        parsed: &Self::Parsed,
+        start: Option<&str>,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        let first_beam = match start {
+            Some(start) => parse_beam(start, parsed)?,
+            None => Beam::new(0, 0, Direction::Right),
+        };
+        record(parsed, &first_beam, recorder);
+        Ok(())
+    }
 }
 
+aoc2023_utils::fixture_tests!(Day16);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc2023_utils::frames::FrameFormat;
+    use std::fs;
 
     const SAMPLE_INPUT_1: &str = concat!(
         ".|...\\....\n",
@@ -282,30 +318,71 @@ mod tests {
     fn test_parse_input() {
         assert_eq!(
             parse_input(SAMPLE_INPUT_1),
-            vec![
-                ".|...\\....".to_string(),
-                "|.-.\\.....".to_string(),
-                ".....|-...".to_string(),
-                "........|.".to_string(),
-                "..........".to_string(),
-                ".........\\".to_string(),
-                "..../.\\\\..".to_string(),
-                ".-.-/..|..".to_string(),
-                ".|....-|.\\".to_string(),
-                "..//.|....".to_string(),
-            ]
+            Grid::from_rows(vec![
+                ".|...\\....".chars().collect(),
+                "|.-.\\.....".chars().collect(),
+                ".....|-...".chars().collect(),
+                "........|.".chars().collect(),
+                "..........".chars().collect(),
+                ".........\\".chars().collect(),
+                "..../.\\\\..".chars().collect(),
+                ".-.-/..|..".chars().collect(),
+                ".|....-|.\\".chars().collect(),
+                "..//.|....".chars().collect(),
+            ])
         );
     }
 
     #[test]
     fn test_get_num_energized_from_input() {
-        println!();
         assert_eq!(get_num_energized_from_input(SAMPLE_INPUT_1), 46);
     }
 
     #[test]
     fn test_get_max_num_energized_from_input() {
-        println!();
         assert_eq!(get_max_num_energized_from_input(SAMPLE_INPUT_1), 51);
     }
+
+    #[test]
+    fn test_render() {
+        let image = render(
+            &parse_input(SAMPLE_INPUT_1),
+            &Beam::new(0, 0, Direction::Right),
+        );
+        assert_eq!((image.width(), image.height()), (10, 10));
+        let num_energized = (0..10)
+            .flat_map(|y| (0..10).map(move |x| (x, y)))
+            .filter(|(x, y)| image.get(*x, *y) == ENERGIZED_COLOR)
+            .count();
+        assert_eq!(num_energized, 46);
+        assert_eq!(image.get(9, 9), EMPTY_COLOR);
+        assert_eq!(image.get(3, 9), OPTIC_COLOR);
+    }
+
+    #[test]
+    fn test_parse_beam() {
+        let optics = parse_input(SAMPLE_INPUT_1);
+        assert_eq!(
+            parse_beam("0,3,down", &optics),
+            Ok(Beam::new(0, 3, Direction::Down))
+        );
+        assert!(parse_beam("0,10,down", &optics).is_err());
+        assert!(parse_beam("0,3,sideways", &optics).is_err());
+        assert!(parse_beam("0,3", &optics).is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let dir = std::env::temp_dir().join(format!("aoc_day16_frames_{}", std::process::id()));
+        let optics = parse_input(SAMPLE_INPUT_1);
+        let mut recorder = Recorder::to_files(&dir, FrameFormat::Text, 1);
+        record(&optics, &Beam::new(0, 0, Direction::Right), &mut recorder);
+        let num_frames = recorder.finish().unwrap();
+
+        let frame = |n: usize| fs::read_to_string(dir.join(format!("frame-{n:05}.txt"))).unwrap();
+        assert!(frame(1).starts_with("@|...\\...."));
+        assert!(frame(2).starts_with("#@...\\...."));
+        assert!(!frame(num_frames).contains('@'));
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
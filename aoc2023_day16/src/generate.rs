@@ -0,0 +1,44 @@
+use aoc2023_utils::Rng;
+
+// Width and height of the contraption.
+pub const DEFAULT_SIZE: usize = 110;
+
+const OPTICS: &[u8] = b"/\\|-";
+const OPTIC_CHANCE: f64 = 0.1;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    if rng.chance(OPTIC_CHANCE) {
+                        *rng.choose(OPTICS) as char
+                    } else {
+                        '.'
+                    }
+                })
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day16;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 20), generate(1, 20));
+        assert_ne!(generate(1, 20), generate(2, 20));
+
+        let parsed = Day16.parse(&generate(3, 20));
+        assert_eq!(parsed.bounds(), (20, 20));
+        let part1 = Day16.part1(&parsed);
+        assert!(part1 > 0);
+        assert!(Day16.part2(&parsed) >= part1);
+    }
+}
@@ -0,0 +1,7 @@
+mod day16;
+pub mod generate;
+
+pub use day16::{
+    get_max_num_energized, get_max_num_energized_from_input, get_num_energized,
+    get_num_energized_from_input, parse_beam, parse_input, record, render, Beam, Day16, Optics,
+};
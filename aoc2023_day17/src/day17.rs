@@ -1,85 +1,36 @@
-const MAX_STRAIGHT_STEPS: u32 = 3;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Coord {
-    row: usize,
-    col: usize,
-}
-
-impl Coord {
-    pub fn new(row: usize, col: usize) -> Self {
-        Self { row, col }
-    }
-}
+use aoc2023_utils::frames::{Frame, Recorder};
+use aoc2023_utils::search::{self, Path, Problem, Visit};
+use aoc2023_utils::{Coord, Direction, Grid, Image, Rgb, Solution};
 
-#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
-enum Dir {
-    Up = 0,
-    Right = 1,
-    Down = 2,
-    Left = 3,
-}
+const MAX_STRAIGHT_STEPS: u32 = 3;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct PathHead {
     coord: Coord,
-    dir: Dir,
+    dir: Direction,
     straight_steps: u32,
-    heat_disipation: u32,
 }
 
 impl PathHead {
-    pub fn new(
-        row: usize,
-        col: usize,
-        dir: Dir,
-        straight_steps: u32,
-        heat_disipation: u32,
-    ) -> Self {
+    pub fn new(row: usize, col: usize, dir: Direction, straight_steps: u32) -> Self {
         Self {
             coord: Coord::new(row, col),
             dir,
             straight_steps,
-            heat_disipation,
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct StepRecord {
-    min_disipations: Vec<[u32; 4]>,
-}
-
-impl StepRecord {
-    pub fn new() -> Self {
-        Self {
-            min_disipations: vec![[u32::MAX; 4]; MAX_STRAIGHT_STEPS as usize],
-        }
-    }
-}
+pub type HeatMap = Grid<u32>;
 
-type HeatMap = Vec<Vec<u32>>;
-type MapStepRecord = Vec<Vec<StepRecord>>;
-
-fn parse_input(s: &str) -> HeatMap {
-    s.lines()
-        .map(|line| {
-            line.as_bytes()
-                .iter()
-                .map(|c| (c - '0' as u8) as u32)
-                .collect::<Vec<u32>>()
-        })
-        .collect::<HeatMap>()
-}
+const ROUTE_COLOR: Rgb = [255, 40, 40];
+const PATH_HEAD_COLOR: Rgb = [255, 255, 255];
 
-fn gen_map_step_record(heat_map: &HeatMap) -> MapStepRecord {
-    heat_map
-        .iter()
-        .map(|row| vec![StepRecord::new(); row.len()])
-        .collect()
+pub fn parse_input(s: &str) -> HeatMap {
+    Grid::parse(s, |c| c.to_digit(10).unwrap())
 }
 
-fn get_straight_step_count_for_dir(path_head: &PathHead, dir: Dir) -> u32 {
+fn get_straight_step_count_for_dir(path_head: &PathHead, dir: Direction) -> u32 {
     if path_head.dir == dir {
         path_head.straight_steps + 1
     } else {
@@ -87,285 +38,254 @@ fn get_straight_step_count_for_dir(path_head: &PathHead, dir: Dir) -> u32 {
     }
 }
 
-fn move_path_up(path_head: &PathHead) -> Option<PathHead> {
-    let row = path_head.coord.row;
-    let col = path_head.coord.col;
-    let step_count = get_straight_step_count_for_dir(path_head, Dir::Up);
-    if row > 0 {
-        Some(PathHead::new(
-            row - 1,
-            col,
-            Dir::Up,
-            step_count,
-            path_head.heat_disipation,
-        ))
-    } else {
-        None
-    }
+fn move_path(path_head: &PathHead, dir: Direction, bounds: (usize, usize)) -> Option<PathHead> {
+    let coord = path_head.coord.step(dir, bounds)?;
+    Some(PathHead {
+        coord,
+        dir,
+        straight_steps: get_straight_step_count_for_dir(path_head, dir),
+    })
 }
 
-fn move_path_down(path_head: &PathHead, max_rows: usize) -> Option<PathHead> {
-    let row = path_head.coord.row;
-    let col = path_head.coord.col;
-    let step_count = get_straight_step_count_for_dir(path_head, Dir::Down);
-    if row < (max_rows - 1) {
-        Some(PathHead::new(
-            row + 1,
-            col,
-            Dir::Down,
-            step_count,
-            path_head.heat_disipation,
-        ))
-    } else {
-        None
-    }
+fn end_coord(heat_map: &HeatMap) -> Coord {
+    Coord::new(heat_map.num_rows() - 1, heat_map.num_cols() - 1)
 }
 
-fn move_path_left(path_head: &PathHead) -> Option<PathHead> {
-    let row = path_head.coord.row;
-    let col = path_head.coord.col;
-    let step_count = get_straight_step_count_for_dir(path_head, Dir::Left);
-    if col > 0 {
-        Some(PathHead::new(
-            row,
-            col - 1,
-            Dir::Left,
-            step_count,
-            path_head.heat_disipation,
-        ))
-    } else {
-        None
+// Getting the crucible from the top left to the bottom right, losing the heat
+// of every block it moves into.
+struct Crucible<'a> {
+    heat_map: &'a HeatMap,
+    // The least any one block loses, which is 0 if the map has any 0s.
+    min_heat: u32,
+}
+
+impl<'a> Crucible<'a> {
+    fn new(heat_map: &'a HeatMap) -> Self {
+        let min_heat = heat_map.iter().map(|(_, heat)| *heat).min().unwrap_or(0);
+        Self { heat_map, min_heat }
     }
 }
 
-fn move_path_right(path_head: &PathHead, max_cols: usize) -> Option<PathHead> {
-    let row = path_head.coord.row;
-    let col = path_head.coord.col;
-    let step_count = get_straight_step_count_for_dir(path_head, Dir::Right);
-    if col < (max_cols - 1) {
-        Some(PathHead::new(
-            row,
-            col + 1,
-            Dir::Right,
-            step_count,
-            path_head.heat_disipation,
-        ))
-    } else {
-        None
+impl Problem for Crucible<'_> {
+    type State = PathHead;
+    type Cost = u32;
+
+    fn starts(&self) -> Vec<PathHead> {
+        vec![
+            PathHead::new(0, 0, Direction::Right, 0),
+            PathHead::new(0, 0, Direction::Down, 0),
+        ]
+    }
+
+    fn successors(&self, path_head: &PathHead) -> Vec<PathHead> {
+        let bounds = self.heat_map.bounds();
+        let mut dirs = vec![path_head.dir.turn_left(), path_head.dir.turn_right()];
+        if path_head.straight_steps < MAX_STRAIGHT_STEPS {
+            dirs.push(path_head.dir);
+        }
+        dirs.into_iter()
+            .filter_map(|dir| move_path(path_head, dir, bounds))
+            .collect()
+    }
+
+    fn cost(&self, _from: &PathHead, to: &PathHead) -> u32 {
+        self.heat_map[to.coord]
+    }
+
+    fn is_goal(&self, path_head: &PathHead) -> bool {
+        path_head.coord == end_coord(self.heat_map)
+    }
+
+    // Every block loses at least the map's least heat, so the rest of the way
+    // loses at least that much for each block left to go.
+    fn heuristic(&self, path_head: &PathHead) -> u32 {
+        let end = end_coord(self.heat_map);
+        (end.row - path_head.coord.row + end.col - path_head.coord.col) as u32 * self.min_heat
     }
 }
 
-fn step_path(
-    path_head: &PathHead,
+// `on_visit` sees each path head as the search settles on it.
+fn find_min_route(
     heat_map: &HeatMap,
-) -> (Option<PathHead>, Option<PathHead>, Option<PathHead>) {
-    let max_rows = heat_map.len();
-    let max_cols = heat_map[0].len();
-
-    let new_path_head1 = if path_head.straight_steps < MAX_STRAIGHT_STEPS {
-        match path_head.dir {
-            Dir::Up => move_path_up(path_head),
-            Dir::Down => move_path_down(path_head, max_rows),
-            Dir::Left => move_path_left(path_head),
-            Dir::Right => move_path_right(path_head, max_cols),
-        }
-    } else {
-        None
-    };
-
-    let new_path_head2 = match path_head.dir {
-        Dir::Up => move_path_left(path_head),
-        Dir::Down => move_path_right(path_head, max_cols),
-        Dir::Left => move_path_down(path_head, max_rows),
-        Dir::Right => move_path_up(path_head),
-    };
-
-    let new_path_head3 = match path_head.dir {
-        Dir::Up => move_path_right(path_head, max_cols),
-        Dir::Down => move_path_left(path_head),
-        Dir::Left => move_path_up(path_head),
-        Dir::Right => move_path_down(path_head, max_rows),
-    };
-
-    (new_path_head1, new_path_head2, new_path_head3)
+    on_visit: impl FnMut(&Visit<PathHead, u32>),
+) -> Path<PathHead, u32> {
+    search::shortest_path_with(&Crucible::new(heat_map), on_visit)
+        .expect("there's always a way to the end")
 }
 
-fn apply_heat_map_to_path(path_head: &mut PathHead, heat_map: &HeatMap) {
-    let row = path_head.coord.row;
-    let col = path_head.coord.col;
-    let heat = heat_map[row][col];
-    path_head.heat_disipation += heat;
+pub fn get_min_disipation(heat_map: &HeatMap) -> u32 {
+    find_min_route(heat_map, |_| {}).cost
 }
 
-fn filter_paths(map_step_record: &MapStepRecord, path_heads: &[PathHead]) -> Vec<PathHead> {
-    path_heads
-        .iter()
-        .filter(|path_head| {
-            let step_record = &map_step_record[path_head.coord.row][path_head.coord.col];
-            let step_idx = (path_head.straight_steps - 1) as usize;
-            let dir_idx = path_head.dir as usize;
-            if step_idx >= 3 {
-                println!("wtf? step_idx is {step_idx}!");
-            }
-            let prev_disipation = step_record.min_disipations[step_idx][dir_idx];
-            path_head.heat_disipation < prev_disipation
-        })
-        .map(|beam| beam.clone())
-        .collect()
+// The blocks on a least-heat route, start to end.
+pub fn get_min_route(heat_map: &HeatMap) -> Vec<Coord> {
+    route_coords(&find_min_route(heat_map, |_| {}))
 }
 
-fn filter_finished_paths(
-    heat_map: &HeatMap,
-    path_heads: &[PathHead],
-) -> Vec<PathHead> {
-    path_heads
+fn route_coords(path: &Path<PathHead, u32>) -> Vec<Coord> {
+    path.states
         .iter()
-        .filter(|path_head| {
-            let end_coord = Coord::new(heat_map.len() - 1, heat_map[0].len() - 1);
-            path_head.coord != end_coord
-        })
-        .map(|path_head| path_head.clone())
+        .map(|path_head| path_head.coord)
         .collect()
 }
 
-fn update_map_step_records(map_step_record: &mut MapStepRecord, path_heads: &[PathHead]) {
-    path_heads.iter().for_each(|path_head| {
-        let step_record = &mut map_step_record[path_head.coord.row][path_head.coord.col];
-        let mut step_idx = (path_head.straight_steps - 1) as usize;
-        let dir_idx = path_head.dir as usize;
-        while step_idx < step_record.min_disipations.len() {
-            let prev_disipation = step_record.min_disipations[step_idx][dir_idx];
-            let new_disipation = prev_disipation.min(path_head.heat_disipation);
-            step_record.min_disipations[step_idx][dir_idx] = new_disipation;
-
-            step_idx += 1;
-        }
-    });
+// Hotter blocks brighter, and green once a path has got to them.
+fn heat_color(heat: u32, reached: bool) -> Rgb {
+    let level = (heat * 255 / 9) as u8;
+    if reached {
+        [level / 4, level, level / 2]
+    } else {
+        [level / 2, level / 2, level]
+    }
 }
 
-fn filter_paths_by_combat(path_heads: &[PathHead]) -> Vec<PathHead> {
-    let mut path_heads = path_heads.to_vec();
-    path_heads.sort_by(|a, b| {
-        if a.coord.row != b.coord.row {
-            a.coord.row.partial_cmp(&b.coord.row).unwrap()
-        } else if a.coord.col != b.coord.col {
-            a.coord.col.partial_cmp(&b.coord.col).unwrap()
-        } else if a.dir != b.dir {
-            a.dir.partial_cmp(&a.dir).unwrap()
-        } else if a.straight_steps != b.straight_steps {
-            a.straight_steps.partial_cmp(&b.straight_steps).unwrap()
-        } else {
-            a.heat_disipation.partial_cmp(&b.heat_disipation).unwrap()
-        }
-    });
+struct SearchFrame<'a> {
+    heat_map: &'a HeatMap,
+    reached: &'a Grid<bool>,
+    path_heads: &'a [PathHead],
+}
 
-    let mut new_paths = vec![];
-    new_paths.reserve(path_heads.len());
+impl Frame for SearchFrame<'_> {
+    fn to_text(&self) -> String {
+        let mut tiles = self
+            .heat_map
+            .map(|heat| char::from_digit(*heat, 10).unwrap());
+        self.reached
+            .iter()
+            .filter(|(_, reached)| **reached)
+            .for_each(|(coord, _)| tiles[coord] = '.');
+        self.path_heads
+            .iter()
+            .for_each(|path_head| tiles[path_head.coord] = '@');
+        tiles.to_string_with(|tile| *tile)
+    }
 
-    (0..path_heads.len()).rev().for_each(|i| {
-        if i == 0 {
-            new_paths.push(path_heads[i]);
-            return;
-        }
+    fn to_image(&self) -> Image {
+        let mut image = Image::from_grid(self.heat_map, |heat| heat_color(*heat, false));
+        self.reached
+            .iter()
+            .filter(|(_, reached)| **reached)
+            .for_each(|((row, col), _)| {
+                image.set(col, row, heat_color(self.heat_map[(row, col)], true))
+            });
+        self.path_heads.iter().for_each(|path_head| {
+            image.set(path_head.coord.col, path_head.coord.row, PATH_HEAD_COLOR)
+        });
+        image
+    }
+}
 
-        let mut prev = i - 1;
-        let mut found_better = false;
-        loop {
-            if path_heads[prev].coord != path_heads[i].coord {
-                break;
-            }
-            if path_heads[prev].dir != path_heads[i].dir {
-                break;
-            }
-            if path_heads[prev].heat_disipation <= path_heads[i].heat_disipation {
-                found_better = true;
-                break;
-            }
-            if prev == 0 {
-                break;
-            }
-
-            prev -= 1;
-        }
+struct RouteFrame<'a> {
+    heat_map: &'a HeatMap,
+    route: &'a [Coord],
+}
 
-        if !found_better {
-            new_paths.push(path_heads[i]);
-        }
-    });
+impl Frame for RouteFrame<'_> {
+    fn to_text(&self) -> String {
+        let mut tiles = self
+            .heat_map
+            .map(|heat| char::from_digit(*heat, 10).unwrap());
+        self.route.iter().for_each(|coord| tiles[*coord] = '#');
+        tiles.to_string_with(|tile| *tile)
+    }
 
-    new_paths
+    fn to_image(&self) -> Image {
+        let mut image = Image::from_grid(self.heat_map, |heat| heat_color(*heat, false));
+        self.route
+            .iter()
+            .for_each(|coord| image.set(coord.col, coord.row, ROUTE_COLOR));
+        image
+    }
 }
 
-fn step(
-    map_step_record: &mut MapStepRecord,
-    path_heads: &[PathHead],
-    heat_map: &HeatMap,
-) -> Vec<PathHead> {
-    let mut new_path_heads = vec![];
+pub fn render(heat_map: &HeatMap) -> Image {
+    RouteFrame {
+        heat_map,
+        route: &get_min_route(heat_map),
+    }
+    .to_image()
+}
 
-    path_heads.iter().for_each(|path_head| {
-        let (head1, head2, head3) = step_path(path_head, heat_map);
-        if let Some(head1) = head1 {
-            new_path_heads.push(head1);
-        }
-        if let Some(head2) = head2 {
-            new_path_heads.push(head2);
-        }
-        if let Some(head3) = head3 {
-            new_path_heads.push(head3);
+// A frame for each estimate of the total heat lost the search works through,
+// showing the path heads it settled on with that estimate, then one of the
+// route it found.
+pub fn record_search(heat_map: &HeatMap, recorder: &mut Recorder) {
+    let mut reached = heat_map.map(|_| false);
+    let mut path_heads = vec![];
+    let mut estimate = 0;
+    let path = find_min_route(heat_map, |visit| {
+        if visit.estimate != estimate && !path_heads.is_empty() {
+            recorder.record(&SearchFrame {
+                heat_map,
+                reached: &reached,
+                path_heads: &path_heads,
+            });
+            path_heads.clear();
         }
+        estimate = visit.estimate;
+        reached[visit.state.coord] = true;
+        path_heads.push(*visit.state);
     });
+    recorder.record(&SearchFrame {
+        heat_map,
+        reached: &reached,
+        path_heads: &path_heads,
+    });
+    recorder.record(&RouteFrame {
+        heat_map,
+        route: &route_coords(&path),
+    });
+}
 
-    let mut new_path_heads = filter_paths_by_combat(&new_path_heads);
+pub fn get_min_disipation_from_input(s: &str) -> u32 {
+    let heat_map = parse_input(s);
+    get_min_disipation(&heat_map)
+}
 
-    new_path_heads.iter_mut().for_each(|new_path_head| {
-        apply_heat_map_to_path(new_path_head, heat_map);
-    });
+pub struct Day17;
 
-    let new_path_heads = filter_paths(map_step_record, &new_path_heads);
+impl Solution for Day17 {
+    type Parsed = HeatMap;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    update_map_step_records(map_step_record, &new_path_heads);
+    const HAS_PART2: bool = false;
 
-    let new_path_heads = filter_finished_paths(heat_map, &new_path_heads);
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
 
-    new_path_heads
-}
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+        get_min_disipation(parsed)
+    }
 
-fn get_min_disipation(heat_map: &HeatMap) -> u32 {
-    let mut map_step_record = gen_map_step_record(heat_map);
-    let mut path_heads = vec![
-        PathHead::new(0, 0, Dir::Right, 0, 0),
-        PathHead::new(0, 0, Dir::Down, 0, 0),
-    ];
-
-    // update_map_step_records(&mut map_step_record, &path_heads);
-
-    let mut steps = 0 as u64;
-    while !path_heads.is_empty() {
-        path_heads = step(&mut map_step_record, &path_heads, &heat_map);
-        steps += 1;
-        // if steps % 1000 == 0 {
-        println!("{} steps: num heads: {}", steps, path_heads.len());
-        // }
+    fn render(&self, parsed: &Self::Parsed) -> Option<Image> {
+        Some(render(parsed))
     }
 
-    let end_record = map_step_record.last().unwrap().last().unwrap();
-    end_record
-        .min_disipations
-        .iter()
-        .map(|dir_recs| *(dir_recs.iter().min().unwrap()))
-        .min()
-        .unwrap()
+    // The crucible always starts in the top left.
+    fn record(
+        &self,
+        parsed: &Self::Parsed,
+        start: Option<&str>,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        if start.is_some() {
+            return Err("day17 always starts in the top left".to_string());
+        }
+        record_search(parsed, recorder);
+        Ok(())
+    }
 }
 
-pub fn get_min_disipation_from_input(s: &str) -> u32 {
-    let heat_map = parse_input(s);
-    get_min_disipation(&heat_map)
-}
+aoc2023_utils::fixture_tests!(Day17);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc2023_utils::frames::FrameFormat;
+    use aoc2023_utils::Rng;
+    use std::fs;
 
     const SAMPLE_INPUT_1: &str = concat!(
         "2413432311323\n",
@@ -385,13 +305,91 @@ mod tests {
 
     #[test]
     fn test_get_min_disipation_from_input() {
-        println!();
         assert_eq!(get_min_disipation_from_input(SAMPLE_INPUT_1), 102);
     }
 
-    // #[test]
-    // fn test_get_max_num_energized_from_input() {
-    //     println!();
-    //     assert_eq!(get_max_num_energized_from_input(SAMPLE_INPUT_1), 51);
-    // }
+    #[test]
+    fn test_record_search() {
+        let dir = std::env::temp_dir().join(format!("aoc_day17_frames_{}", std::process::id()));
+        let heat_map = parse_input(SAMPLE_INPUT_1);
+        let mut recorder = Recorder::to_files(&dir, FrameFormat::Text, 1);
+        record_search(&heat_map, &mut recorder);
+        let num_frames = recorder.finish().unwrap();
+
+        let frame = |n: usize| fs::read_to_string(dir.join(format!("frame-{n:05}.txt"))).unwrap();
+        assert!(frame(1).starts_with("@41"));
+        assert!(frame(num_frames - 1).ends_with('@'));
+        let route_len = get_min_route(&heat_map).len();
+        assert_eq!(frame(num_frames).matches('#').count(), route_len);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_min_route() {
+        let inputs = [SAMPLE_INPUT_1.to_string()]
+            .into_iter()
+            .chain((0..20).map(|seed| crate::generate::generate(seed, 2 + seed as usize)));
+        for input in inputs {
+            let heat_map = parse_input(&input);
+            let route = get_min_route(&heat_map);
+            assert_eq!(route.first(), Some(&Coord::new(0, 0)));
+            assert_eq!(route.last(), Some(&end_coord(&heat_map)));
+
+            let heat = route[1..].iter().map(|coord| heat_map[*coord]).sum::<u32>();
+            assert_eq!(heat, get_min_disipation(&heat_map));
+
+            // One block at a time, never back, and never more than 3 straight.
+            let dirs = route
+                .windows(2)
+                .map(|pair| {
+                    let (from, to) = (pair[0], pair[1]);
+                    *Direction::ALL
+                        .iter()
+                        .find(|dir| from.step(**dir, heat_map.bounds()) == Some(to))
+                        .unwrap()
+                })
+                .collect::<Vec<Direction>>();
+            assert!(dirs.windows(2).all(|pair| pair[1] != pair[0].opposite()));
+            assert!(dirs
+                .windows(4)
+                .all(|run| run.iter().any(|dir| *dir != run[0])));
+        }
+    }
+
+    #[test]
+    fn test_zero_heat_blocks() {
+        // Nearly free along the top and down the right.
+        let heat_map = parse_input("1110\n9990\n9990\n");
+        assert_eq!(get_min_disipation(&heat_map), 2);
+
+        // Against plain Dijkstra. Counting 1 per block left, as if no block
+        // could be free, gets some of these wrong.
+        let mut rng = Rng::new(17);
+        for _ in 0..50 {
+            let heat_map = Grid::from_rows(
+                (0..8)
+                    .map(|_| {
+                        (0..8)
+                            .map(|_| rng.below(10) as u32 * rng.below(2) as u32)
+                            .collect()
+                    })
+                    .collect(),
+            );
+            let dijkstra = Crucible {
+                heat_map: &heat_map,
+                min_heat: 0,
+            };
+            assert_eq!(
+                get_min_disipation(&heat_map),
+                search::shortest_path(&dijkstra).unwrap().cost
+            );
+        }
+    }
+
+    #[test]
+    fn test_real_input() {
+        let input =
+            aoc2023_utils::get_input(concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs/day17.txt"));
+        assert_eq!(get_min_disipation_from_input(&input), 1099);
+    }
 }
@@ -1,8 +0,0 @@
-pub mod day17;
-
-fn main() {
-    let _input = aoc2023_utils::get_input("inputs/day17.txt");
-    // let result = day17::get_max_num_energized_from_input(&input);
-    let result = "day17";
-    println!("{}", result);
-}
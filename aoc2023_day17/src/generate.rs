@@ -0,0 +1,37 @@
+use aoc2023_utils::Rng;
+
+// Width and height of the city.
+pub const DEFAULT_SIZE: usize = 141;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| char::from_digit(rng.range(1..10) as u32, 10).unwrap())
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day17;
+    use aoc2023_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 20), generate(1, 20));
+        assert_ne!(generate(1, 20), generate(2, 20));
+
+        let input = generate(3, 20);
+        assert!(!input.contains('0'));
+
+        // Each of the 38 blocks on the way in costs between 1 and 9.
+        let parsed = Day17.parse(&input);
+        assert!((38..=38 * 9).contains(&Day17.part1(&parsed)));
+    }
+}
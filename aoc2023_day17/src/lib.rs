@@ -0,0 +1,7 @@
+mod day17;
+pub mod generate;
+
+pub use day17::{
+    get_min_disipation, get_min_disipation_from_input, get_min_route, parse_input, record_search,
+    render, Day17, HeatMap,
+};
@@ -0,0 +1,133 @@
+use aoc2023_utils::Part;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.toml";
+
+const HEADER: &str = "# Confirmed answers for inputs/dayNN.txt, checked by `aoc verify`.\n";
+
+// Only the subset of TOML we write ourselves is understood: `[dayNN]` tables
+// holding `a` and `b` keys with string or integer values.
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers {
+    entries: BTreeMap<(u32, char), String>,
+}
+
+fn parse_table_header(line: &str) -> Option<u32> {
+    line.strip_prefix("[day")?.strip_suffix(']')?.parse().ok()
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let value = quoted.strip_suffix('"')?;
+        (!value.contains(['"', '\\'])).then(|| value.to_string())
+    } else {
+        value.parse::<i128>().ok().map(|num| num.to_string())
+    }
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+        let mut day = None;
+
+        for (line_idx, line) in s.lines().enumerate() {
+            let err = |msg: &str| format!("line {}: {msg}", line_idx + 1);
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                day = Some(parse_table_header(line).ok_or(err("bad table header"))?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(err("expected key = value"))?;
+            let day = day.ok_or(err("answer outside of a [dayNN] table"))?;
+            let part = match key.trim() {
+                "a" => Part::A,
+                "b" => Part::B,
+                key => return Err(err(&format!("unknown part: {key}"))),
+            };
+            let value = parse_value(value.trim()).ok_or(err("bad answer value"))?;
+            answers.set(day, part, &value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.entries.get(&(day, part.label())).map(|s| &s[..])
+    }
+
+    pub fn set(&mut self, day: u32, part: Part, answer: &str) {
+        self.entries.insert((day, part.label()), answer.to_string());
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut s = HEADER.to_string();
+        let mut prev_day = None;
+        for ((day, part), answer) in &self.entries {
+            if prev_day != Some(*day) {
+                s += &format!("\n[day{day:02}]\n");
+                prev_day = Some(*day);
+            }
+            s += &format!("{part} = \"{answer}\"\n");
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ANSWERS: &str = concat!(
+        "# Confirmed answers for inputs/dayNN.txt, checked by `aoc verify`.\n",
+        "\n",
+        "[day01]\n",
+        "a = \"142\"\n",
+        "b = \"281\"\n",
+        "\n",
+        "[day17]\n",
+        "a = \"102\"\n",
+    );
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(SAMPLE_ANSWERS).unwrap();
+        assert_eq!(answers.get(1, Part::A), Some("142"));
+        assert_eq!(answers.get(1, Part::B), Some("281"));
+        assert_eq!(answers.get(17, Part::A), Some("102"));
+        assert_eq!(answers.get(17, Part::B), None);
+        assert_eq!(answers.get(2, Part::A), None);
+
+        let answers = Answers::parse("[day9]\n  b = -2\n").unwrap();
+        assert_eq!(answers.get(9, Part::B), Some("-2"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("a = \"1\"\n").is_err());
+        assert!(Answers::parse("[day]\n").is_err());
+        assert!(Answers::parse("[day01]\nc = \"1\"\n").is_err());
+        assert!(Answers::parse("[day01]\na = \"1\n").is_err());
+        assert!(Answers::parse("[day01]\na\n").is_err());
+        assert_eq!(
+            Answers::parse("[day01]\na = 1 # inline comment\n"),
+            Err("line 2: bad answer value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let mut answers = Answers::default();
+        answers.set(17, Part::A, "102");
+        answers.set(1, Part::B, "281");
+        answers.set(1, Part::A, "0");
+        answers.set(1, Part::A, "142");
+        assert_eq!(answers.to_toml(), SAMPLE_ANSWERS);
+        assert_eq!(Answers::parse(&answers.to_toml()), Ok(answers));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::days::Day;
+use aoc2023_utils::Part;
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        // Nearest-rank percentile.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            p95,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    pub fn label(&self) -> String {
+        match self {
+            Stage::Parse => "parse".to_string(),
+            Stage::Solve(part) => part.label().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BenchResult {
+    pub day: u32,
+    pub stage: Stage,
+    pub iterations: u32,
+    pub stats: Stats,
+}
+
+// Runs `f` `warmup` times untimed, then `iterations` times timed.
+fn time_runs<T>(warmup: u32, iterations: u32, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    (0..warmup).for_each(|_| {
+        black_box(f());
+    });
+
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect()
+}
+
+pub fn bench_day(day: &Day, input: &str, warmup: u32, iterations: u32) -> Vec<BenchResult> {
+    let result = |stage, samples: Vec<Duration>| BenchResult {
+        day: day.num,
+        stage,
+        iterations,
+        stats: Stats::from_samples(&samples),
+    };
+
+    let samples = time_runs(warmup, iterations, || day.solution.parse_any(input));
+    let mut results = vec![result(Stage::Parse, samples)];
+
+    let parsed = day.solution.parse_any(input);
+    for part in [Part::A, Part::B] {
+        if day.has_part(part) {
+            let samples = time_runs(warmup, iterations, || {
+                day.solution.solve_any(parsed.as_ref(), part)
+            });
+            results.push(result(Stage::Solve(part), samples));
+        }
+    }
+
+    results
+}
+
+pub fn fmt_duration(duration: Duration) -> String {
+    format!("{:.3?}", duration)
+}
+
+pub fn print_header() {
+    println!(
+        "{:<10} {:>6} {:>12} {:>12} {:>12} {:>12}",
+        "stage", "iters", "min", "median", "mean", "p95"
+    );
+}
+
+pub fn print_result(result: &BenchResult) {
+    println!(
+        "{:<10} {:>6} {:>12} {:>12} {:>12} {:>12}",
+        format!("day{:02}{}", result.day, result.stage.label()),
+        result.iterations,
+        fmt_duration(result.stats.min),
+        fmt_duration(result.stats.median),
+        fmt_duration(result.stats.mean),
+        fmt_duration(result.stats.p95),
+    );
+}
+
+// One row per day and stage, with all times in nanoseconds.
+pub fn write_csv(results: &[BenchResult], mut writer: impl Write) -> io::Result<()> {
+    writeln!(
+        writer,
+        "day,stage,iterations,min_ns,median_ns,mean_ns,p95_ns"
+    )?;
+    for result in results {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            result.day,
+            result.stage.label(),
+            result.iterations,
+            result.stats.min.as_nanos(),
+            result.stats.median.as_nanos(),
+            result.stats.mean.as_nanos(),
+            result.stats.p95.as_nanos(),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = (1..=20).rev().map(ms).collect::<Vec<Duration>>();
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Stats {
+                min: ms(1),
+                median: Duration::from_micros(10500),
+                mean: Duration::from_micros(10500),
+                p95: ms(19),
+            }
+        );
+
+        let samples = [ms(5), ms(1), ms(3)];
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Stats {
+                min: ms(1),
+                median: ms(3),
+                mean: ms(3),
+                p95: ms(5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let results = vec![
+            BenchResult {
+                day: 5,
+                stage: Stage::Parse,
+                iterations: 3,
+                stats: Stats::from_samples(&[ms(5), ms(1), ms(3)]),
+            },
+            BenchResult {
+                day: 5,
+                stage: Stage::Solve(Part::B),
+                iterations: 1,
+                stats: Stats::from_samples(&[Duration::from_nanos(42)]),
+            },
+        ];
+
+        let mut out = vec![];
+        write_csv(&results, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "day,stage,iterations,min_ns,median_ns,mean_ns,p95_ns\n",
+                "5,parse,3,1000000,3000000,3000000,5000000\n",
+                "5,b,1,42,42,42,42\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let day = crate::days::get_day(1).unwrap();
+        let input = "1abc2\npqr3stu8vwx\n";
+        let results = bench_day(day, input, 0, 2);
+        let stages = results
+            .iter()
+            .map(|result| result.stage)
+            .collect::<Vec<Stage>>();
+        assert_eq!(
+            stages,
+            vec![Stage::Parse, Stage::Solve(Part::A), Stage::Solve(Part::B)]
+        );
+        assert!(results.iter().all(|result| result.iterations == 2));
+    }
+}
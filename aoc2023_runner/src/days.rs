@@ -0,0 +1,152 @@
+use aoc2023_utils::{DynSolution, Part};
+
+pub struct Day {
+    pub num: u32,
+    pub solution: &'static (dyn DynSolution + Sync),
+    pub generate: fn(u64, usize) -> String,
+    pub default_gen_size: usize,
+    // The biggest size the generator can make as asked, if it has one.
+    pub max_gen_size: Option<usize>,
+}
+
+pub const MAX_DAY: u32 = 25;
+
+pub fn input_filename(num: u32) -> String {
+    format!("day{num:02}.txt")
+}
+
+impl Day {
+    pub fn input_filename(&self) -> String {
+        input_filename(self.num)
+    }
+
+    pub fn has_part(&self, part: Part) -> bool {
+        self.solution.has_part(part)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        num: 1,
+        solution: &day01::Day01,
+        generate: day01::generate::generate,
+        default_gen_size: day01::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 2,
+        solution: &day02::Day02,
+        generate: day02::generate::generate,
+        default_gen_size: day02::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 3,
+        solution: &day03::Day03,
+        generate: day03::generate::generate,
+        default_gen_size: day03::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 4,
+        solution: &day04::Day04,
+        generate: day04::generate::generate,
+        default_gen_size: day04::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 5,
+        solution: &day05::Day05,
+        generate: day05::generate::generate,
+        default_gen_size: day05::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 6,
+        solution: &day06::Day06,
+        generate: day06::generate::generate,
+        default_gen_size: day06::generate::DEFAULT_SIZE,
+        max_gen_size: Some(day06::generate::MAX_SIZE),
+    },
+    Day {
+        num: 7,
+        solution: &day07::Day07,
+        generate: day07::generate::generate,
+        default_gen_size: day07::generate::DEFAULT_SIZE,
+        max_gen_size: Some(day07::generate::MAX_SIZE),
+    },
+    Day {
+        num: 8,
+        solution: &day08::Day08,
+        generate: day08::generate::generate,
+        default_gen_size: day08::generate::DEFAULT_SIZE,
+        max_gen_size: Some(day08::generate::MAX_SIZE),
+    },
+    Day {
+        num: 9,
+        solution: &day09::Day09,
+        generate: day09::generate::generate,
+        default_gen_size: day09::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 10,
+        solution: &day10::Day10,
+        generate: day10::generate::generate,
+        default_gen_size: day10::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 11,
+        solution: &day11::Day11,
+        generate: day11::generate::generate,
+        default_gen_size: day11::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 12,
+        solution: &day12::Day12,
+        generate: day12::generate::generate,
+        default_gen_size: day12::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 13,
+        solution: &day13::Day13,
+        generate: day13::generate::generate,
+        default_gen_size: day13::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 14,
+        solution: &day14::Day14,
+        generate: day14::generate::generate,
+        default_gen_size: day14::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 15,
+        solution: &day15::Day15,
+        generate: day15::generate::generate,
+        default_gen_size: day15::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 16,
+        solution: &day16::Day16,
+        generate: day16::generate::generate,
+        default_gen_size: day16::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+    Day {
+        num: 17,
+        solution: &day17::Day17,
+        generate: day17::generate::generate,
+        default_gen_size: day17::generate::DEFAULT_SIZE,
+        max_gen_size: None,
+    },
+];
+
+pub fn get_day(num: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.num == num)
+}
@@ -0,0 +1,366 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_CONFIG_PATH: &str = "aoc-config.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const YEAR: u32 = 2023;
+const USER_AGENT: &str = "github.com/jblee123/advent_of_code_2023";
+// Lives next to the cached inputs so every checkout shares one limit per
+// inputs directory.
+const LAST_FETCH_FILE: &str = ".last-fetch";
+
+// Read from a flat `key = value` file:
+//   session = "53616c7465645f5f..."
+//   base_url = "https://adventofcode.com"
+//   min_interval_ms = 5000
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub min_interval: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        for (line_idx, line) in s.lines().enumerate() {
+            let err = |msg: &str| format!("line {}: {msg}", line_idx + 1);
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(err("expected key = value"))?;
+            let value = value.trim();
+            let string_value = || {
+                value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .map(|value| value.to_string())
+                    .ok_or(err("expected a quoted string"))
+            };
+            match key.trim() {
+                // It goes into curl's config unescaped, so nothing but the hex
+                // the site hands out gets through.
+                "session" => {
+                    let session = string_value()?;
+                    if session.is_empty() || !session.chars().all(|c| c.is_ascii_hexdigit()) {
+                        return Err(err("session should be hex digits"));
+                    }
+                    config.session = Some(session);
+                }
+                "base_url" => config.base_url = string_value()?,
+                "min_interval_ms" => {
+                    let ms = value
+                        .parse::<u64>()
+                        .map_err(|_| err("expected an integer"))?;
+                    config.min_interval = Duration::from_millis(ms);
+                }
+                key => return Err(err(&format!("unknown key: {key}"))),
+            }
+        }
+
+        Ok(config)
+    }
+
+    // A missing file is fine as long as every input is already cached.
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|err| format!("{path}: {err}")),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("error reading {path}: {err}")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+pub fn input_url(base_url: &str, day: u32) -> String {
+    format!("{}/{YEAR}/day/{day}/input", base_url.trim_end_matches('/'))
+}
+
+fn time_to_wait(last: Option<SystemTime>, now: SystemTime, min_interval: Duration) -> Duration {
+    let since_last = last
+        .and_then(|last| now.duration_since(last).ok())
+        .unwrap_or(min_interval);
+    min_interval.saturating_sub(since_last)
+}
+
+// Sleeps until at least `min_interval` has passed since the last request made
+// from any run using the same stamp file, then records this request.
+fn wait_for_turn(stamp_path: &Path, min_interval: Duration) -> Result<(), String> {
+    let last = fs::read_to_string(stamp_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+    thread::sleep(time_to_wait(last, SystemTime::now(), min_interval));
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    fs::write(stamp_path, format!("{now_ms}\n"))
+        .map_err(|err| format!("error writing {}: {err}", stamp_path.display()))
+}
+
+// Shells out to curl since std has no TLS. The cookie goes in through stdin so
+// the token never shows up in the process list.
+fn download(url: &str, session: &str, dest: &Path) -> Result<(), String> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--config", "-"])
+        .arg("--user-agent")
+        .arg(USER_AGENT)
+        .arg("--output")
+        .arg(dest)
+        .args(["--write-out", "%{http_code}"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("couldn't run curl: {err}"))?;
+
+    let curl_config = format!("header = \"Cookie: session={session}\"\n");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(curl_config.as_bytes())
+        .map_err(|err| format!("couldn't talk to curl: {err}"))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("couldn't run curl: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "200" => Ok(()),
+        status => Err(format!("{url}: HTTP {status}")),
+    }
+}
+
+pub fn fetch_input(config: &Config, day: u32, dest: &Path) -> Result<Fetched, String> {
+    if dest.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let session = config
+        .session
+        .as_deref()
+        .ok_or("no session token configured")?;
+
+    let dir = dest.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).map_err(|err| format!("error creating {}: {err}", dir.display()))?;
+    wait_for_turn(&dir.join(LAST_FETCH_FILE), config.min_interval)?;
+
+    // Download next to the destination and rename, so a failed or interrupted
+    // download never looks like a cached input.
+    let partial = PathBuf::from(format!("{}.part", dest.display()));
+    let result = download(&input_url(&config.base_url, day), session, &partial)
+        .and_then(|_| fs::rename(&partial, dest).map_err(|err| err.to_string()));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+
+    result.map(|_| Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_fetch_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    // Answers every request with its own path, after recording the request
+    // line and cookie. Anything without the right cookie gets a 400, like the
+    // real site.
+    fn start_mock_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let log = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut has_cookie = false;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    has_cookie |= line.trim() == "Cookie: session=s3cret";
+                }
+
+                let path = request_line.split(' ').nth(1).unwrap().to_string();
+                log.lock().unwrap().push(path.clone());
+                let (status, body) = if has_cookie {
+                    ("200 OK", format!("input for {path}\n"))
+                } else {
+                    ("400 Bad Request", "bad session\n".to_string())
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    #[test]
+    fn test_parse_config() {
+        assert_eq!(
+            Config::parse(concat!(
+                "# adventofcode.com session cookie\n",
+                "session = \"abc123\"\n",
+                "base_url = \"http://localhost:8080\"\n",
+                "min_interval_ms = 250\n",
+            )),
+            Ok(Config {
+                session: Some("abc123".to_string()),
+                base_url: "http://localhost:8080".to_string(),
+                min_interval: Duration::from_millis(250),
+            })
+        );
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert!(Config::parse("session = abc123\n").is_err());
+        assert!(Config::parse("session = \"\"\n").is_err());
+        assert!(Config::parse("session = \"abc123\" -o \"/tmp/x\"\n").is_err());
+        assert!(Config::parse("session = \"abc123\\nurl = evil\"\n").is_err());
+        assert!(Config::parse("min_interval_ms = soon\n").is_err());
+        assert!(Config::parse("token = \"abc123\"\n").is_err());
+    }
+
+    #[test]
+    fn test_input_url() {
+        assert_eq!(
+            input_url("https://adventofcode.com", 5),
+            "https://adventofcode.com/2023/day/5/input"
+        );
+        assert_eq!(
+            input_url("http://localhost:8080/", 17),
+            "http://localhost:8080/2023/day/17/input"
+        );
+    }
+
+    #[test]
+    fn test_time_to_wait() {
+        let now = UNIX_EPOCH + Duration::from_secs(100);
+        let min_interval = Duration::from_secs(5);
+        assert_eq!(time_to_wait(None, now, min_interval), Duration::ZERO);
+        assert_eq!(
+            time_to_wait(Some(now - Duration::from_secs(2)), now, min_interval),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            time_to_wait(Some(now - Duration::from_secs(60)), now, min_interval),
+            Duration::ZERO
+        );
+        // A stamp from the future, e.g. after a clock change, doesn't block.
+        assert_eq!(
+            time_to_wait(Some(now + Duration::from_secs(60)), now, min_interval),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_fetch_input_from_mock_server() {
+        let (base_url, requests) = start_mock_server();
+        let dir = temp_dir("mock");
+        let config = Config {
+            session: Some("s3cret".to_string()),
+            base_url,
+            min_interval: Duration::from_millis(300),
+        };
+
+        let start = Instant::now();
+        let first = fetch_input(&config, 5, &dir.join("day05.txt"));
+        let second = fetch_input(&config, 6, &dir.join("day06.txt"));
+        let elapsed = start.elapsed();
+        let cached = fetch_input(&config, 5, &dir.join("day05.txt"));
+        let day05 = fs::read_to_string(dir.join("day05.txt"));
+
+        let bad_config = Config {
+            session: Some("wrong".to_string()),
+            min_interval: Duration::ZERO,
+            ..config
+        };
+        let rejected = fetch_input(&bad_config, 7, &dir.join("day07.txt"));
+        let day07_exists = dir.join("day07.txt").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, Ok(Fetched::Downloaded));
+        assert_eq!(second, Ok(Fetched::Downloaded));
+        assert_eq!(cached, Ok(Fetched::Cached));
+        assert_eq!(day05.unwrap(), "input for /2023/day/5/input\n");
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(rejected.unwrap_err().contains("HTTP 400"));
+        assert!(!day07_exists);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "/2023/day/5/input",
+                "/2023/day/6/input",
+                "/2023/day/7/input"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_input_needs_session_only_when_not_cached() {
+        let dir = temp_dir("session");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day01.txt"), "cached\n").unwrap();
+
+        let config = Config::default();
+        let cached = fetch_input(&config, 1, &dir.join("day01.txt"));
+        let missing = fetch_input(&config, 2, &dir.join("day02.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(cached, Ok(Fetched::Cached));
+        assert_eq!(missing, Err("no session token configured".to_string()));
+    }
+}
@@ -0,0 +1,89 @@
+use aoc2023_utils::Part;
+use std::fmt::Write;
+use std::time::Duration;
+
+// One line of `aoc run --format json` output. A part that ran out of time has
+// no answer.
+#[derive(Debug, PartialEq)]
+pub struct RunRecord<'a> {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Option<&'a str>,
+    pub elapsed: Duration,
+    pub input_path: &'a str,
+}
+
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl RunRecord<'_> {
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"timed_out\":{},\"elapsed_ms\":{:.3},\"input_path\":{}}}",
+            self.day,
+            escape(&self.part.label().to_string()),
+            self.answer.map_or("null".to_string(), escape),
+            self.answer.is_none(),
+            self.elapsed.as_secs_f64() * 1000.0,
+            escape(self.input_path),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("142"), "\"142\"");
+        assert_eq!(escape("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(escape("#.\n.#\u{1}"), "\"#.\\n.#\\u0001\"");
+    }
+
+    #[test]
+    fn test_run_record_to_json() {
+        let record = RunRecord {
+            day: 5,
+            part: Part::B,
+            answer: Some("46"),
+            elapsed: Duration::from_micros(1234),
+            input_path: "inputs/day05.txt",
+        };
+        assert_eq!(
+            record.to_json(),
+            concat!(
+                "{\"day\":5,\"part\":\"b\",\"answer\":\"46\",\"timed_out\":false,",
+                "\"elapsed_ms\":1.234,\"input_path\":\"inputs/day05.txt\"}"
+            )
+        );
+
+        let record = RunRecord {
+            answer: None,
+            elapsed: Duration::from_secs(2),
+            ..record
+        };
+        assert_eq!(
+            record.to_json(),
+            concat!(
+                "{\"day\":5,\"part\":\"b\",\"answer\":null,\"timed_out\":true,",
+                "\"elapsed_ms\":2000.000,\"input_path\":\"inputs/day05.txt\"}"
+            )
+        );
+    }
+}
@@ -0,0 +1,1479 @@
+mod answers;
+mod bench;
+mod days;
+mod fetch;
+mod json;
+mod repl;
+mod scaffold;
+
+use answers::{Answers, DEFAULT_ANSWERS_PATH};
+use aoc2023_utils::frames::{FrameFormat, Recorder};
+use aoc2023_utils::log::{self, Level};
+use aoc2023_utils::{cancel, par, InputError, InputSource, Part};
+use days::{Day, DAYS, MAX_DAY};
+use fetch::{Config, Fetched, DEFAULT_CONFIG_PATH};
+use json::RunRecord;
+use repl::Repl;
+use std::any::Any;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, ErrorKind};
+use std::path::Path;
+use std::process::ExitCode;
+use std::slice;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = concat!(
+    "usage: aoc [-v|-q]... <command>\n",
+    "       aoc run <day|all> [a|b|all] [--input <path|->] [--format text|json]\n",
+    "               [--timeout <secs>]\n",
+    "       aoc bench <day|all> [--iterations <n>] [--warmup <n>] [--input <path|->]\n",
+    "                 [--output <path>]\n",
+    "       aoc verify [day|all] [--record [--overwrite]] [--answers <path>]\n",
+    "                  [--timeout <secs>]\n",
+    "       aoc fetch <day|all> [--config <path>] [--base-url <url>]\n",
+    "       aoc generate <day> [--seed <n>] [--size <n>]\n",
+    "       aoc new <day>\n",
+    "       aoc repl <day> [--input <path>]\n",
+    "       aoc render <day> [--input <path|->] [--output <path>] [--scale <n>]\n",
+    "       aoc record <day> [--input <path|->] [--output <dir|path.gif>]\n",
+    "                  [--format txt|ppm|png] [--scale <n>] [--start <start>]",
+);
+
+const DEFAULT_BENCH_ITERATIONS: u32 = 10;
+const DEFAULT_BENCH_WARMUP: u32 = 1;
+const DEFAULT_BENCH_OUTPUT: &str = "bench-results.csv";
+const DEFAULT_RENDER_SCALE: usize = 4;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum DaySel {
+    One(u32),
+    All,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum PartSel {
+    One(Part),
+    All,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day_sel: DaySel,
+    part_sel: PartSel,
+    input: Option<String>,
+    format: Format,
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchArgs {
+    day_sel: DaySel,
+    input: Option<String>,
+    iterations: u32,
+    warmup: u32,
+    output: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct VerifyArgs {
+    day_sel: DaySel,
+    record: bool,
+    overwrite: bool,
+    answers: String,
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq)]
+struct FetchArgs {
+    day_nums: Vec<u32>,
+    config: String,
+    base_url: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct GenerateArgs {
+    day_num: u32,
+    seed: u64,
+    size: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+struct NewArgs {
+    day_num: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct ReplArgs {
+    day_num: u32,
+    input: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct RenderArgs {
+    day_num: u32,
+    input: Option<String>,
+    output: String,
+    scale: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct RecordArgs {
+    day_num: u32,
+    input: Option<String>,
+    output: String,
+    // None for a GIF.
+    format: Option<FrameFormat>,
+    scale: usize,
+    start: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Verify(VerifyArgs),
+    Fetch(FetchArgs),
+    Generate(GenerateArgs),
+    New(NewArgs),
+    Repl(ReplArgs),
+    Render(RenderArgs),
+    Record(RecordArgs),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Check {
+    Pass,
+    Fail,
+    New,
+    NotImplemented,
+    Timeout,
+    Error,
+}
+
+impl Check {
+    fn label(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail => "FAIL",
+            Check::New => "new",
+            Check::NotImplemented => "-",
+            Check::Timeout => "TIMEOUT",
+            Check::Error => "error",
+        }
+    }
+}
+
+fn parse_day_sel(s: &str) -> Result<DaySel, String> {
+    if s == "all" {
+        return Ok(DaySel::All);
+    }
+
+    let num = s.parse::<u32>().map_err(|_| format!("bad day: {s}"))?;
+    if days::get_day(num).is_none() {
+        return Err(format!("no solution for day {num}"));
+    }
+
+    Ok(DaySel::One(num))
+}
+
+fn parse_part_sel(s: &str) -> Result<PartSel, String> {
+    match s {
+        "a" => Ok(PartSel::One(Part::A)),
+        "b" => Ok(PartSel::One(Part::B)),
+        "all" => Ok(PartSel::All),
+        _ => Err(format!("bad part: {s}")),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("bad format: {s}")),
+    }
+}
+
+fn next_value<'a>(args: &mut slice::Iter<&'a str>, flag: &str) -> Result<&'a str, String> {
+    args.next()
+        .copied()
+        .ok_or(format!("missing value for {flag}"))
+}
+
+fn parse_count(s: &str, flag: &str, min: u32) -> Result<u32, String> {
+    s.parse::<u32>()
+        .ok()
+        .filter(|n| *n >= min)
+        .ok_or(format!("bad value for {flag}: {s}"))
+}
+
+fn parse_timeout(s: &str, flag: &str) -> Result<Duration, String> {
+    Ok(Duration::from_secs(parse_count(s, flag, 1)? as u64))
+}
+
+fn check_input_day_sel(input: &Option<String>, day_sel: DaySel) -> Result<(), String> {
+    if input.is_some() && day_sel == DaySel::All {
+        return Err("--input needs a single day".to_string());
+    }
+    Ok(())
+}
+
+fn parse_run_args(args: &[&str]) -> Result<RunArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut timeout = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--input" | "-i" => input = Some(next_value(&mut args, arg)?.to_string()),
+            "--format" => format = parse_format(next_value(&mut args, arg)?)?,
+            "--timeout" => timeout = Some(parse_timeout(next_value(&mut args, arg)?, arg)?),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_sel = parse_day_sel(positional.next().ok_or("missing day")?)?;
+    let part_sel = match positional.next() {
+        Some(part_str) => parse_part_sel(part_str)?,
+        None => PartSel::All,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    check_input_day_sel(&input, day_sel)?;
+
+    Ok(RunArgs {
+        day_sel,
+        part_sel,
+        input,
+        format,
+        timeout,
+    })
+}
+
+fn parse_bench_args(args: &[&str]) -> Result<BenchArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+    let mut iterations = DEFAULT_BENCH_ITERATIONS;
+    let mut warmup = DEFAULT_BENCH_WARMUP;
+    let mut output = DEFAULT_BENCH_OUTPUT.to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--input" | "-i" => input = Some(next_value(&mut args, arg)?.to_string()),
+            "--iterations" | "-n" => iterations = parse_count(next_value(&mut args, arg)?, arg, 1)?,
+            "--warmup" => warmup = parse_count(next_value(&mut args, arg)?, arg, 0)?,
+            "--output" | "-o" => output = next_value(&mut args, arg)?.to_string(),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_sel = parse_day_sel(positional.next().ok_or("missing day")?)?;
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    check_input_day_sel(&input, day_sel)?;
+
+    Ok(BenchArgs {
+        day_sel,
+        input,
+        iterations,
+        warmup,
+        output,
+    })
+}
+
+fn parse_verify_args(args: &[&str]) -> Result<VerifyArgs, String> {
+    let mut positional = vec![];
+    let mut record = false;
+    let mut overwrite = false;
+    let mut answers = DEFAULT_ANSWERS_PATH.to_string();
+    let mut timeout = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--record" => record = true,
+            "--overwrite" => overwrite = true,
+            "--answers" => answers = next_value(&mut args, arg)?.to_string(),
+            "--timeout" => timeout = Some(parse_timeout(next_value(&mut args, arg)?, arg)?),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_sel = match positional.next() {
+        Some(day_str) => parse_day_sel(day_str)?,
+        None => DaySel::All,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+    if overwrite && !record {
+        return Err("--overwrite only goes with --record".to_string());
+    }
+
+    Ok(VerifyArgs {
+        day_sel,
+        record,
+        overwrite,
+        answers,
+        timeout,
+    })
+}
+
+// Any day of the calendar, whether or not it has a solution.
+fn parse_day_num(s: &str) -> Result<u32, String> {
+    s.parse::<u32>()
+        .ok()
+        .filter(|num| (1..=MAX_DAY).contains(num))
+        .ok_or(format!("bad day: {s}"))
+}
+
+fn parse_fetch_args(args: &[&str]) -> Result<FetchArgs, String> {
+    let mut positional = vec![];
+    let mut config = DEFAULT_CONFIG_PATH.to_string();
+    let mut base_url = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--config" => config = next_value(&mut args, arg)?.to_string(),
+            "--base-url" => base_url = Some(next_value(&mut args, arg)?.to_string()),
+            _ => positional.push(*arg),
+        }
+    }
+
+    // Unlike the other commands, a single day doesn't need a solution yet:
+    // fetching the input usually comes first.
+    let mut positional = positional.into_iter();
+    let day_nums = match positional.next().ok_or("missing day")? {
+        "all" => DAYS.iter().map(|day| day.num).collect(),
+        day_str => vec![parse_day_num(day_str)?],
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    Ok(FetchArgs {
+        day_nums,
+        config,
+        base_url,
+    })
+}
+
+fn parse_generate_args(args: &[&str]) -> Result<GenerateArgs, String> {
+    let mut positional = vec![];
+    let mut seed = 0;
+    let mut size = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--seed" => {
+                let seed_str = next_value(&mut args, arg)?;
+                seed = seed_str
+                    .parse::<u64>()
+                    .map_err(|_| format!("bad value for {arg}: {seed_str}"))?;
+            }
+            "--size" => size = Some(parse_count(next_value(&mut args, arg)?, arg, 1)? as usize),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_num = match parse_day_sel(positional.next().ok_or("missing day")?)? {
+        DaySel::One(num) => num,
+        DaySel::All => return Err("generate needs a single day".to_string()),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+    let max_size = days::get_day(day_num).unwrap().max_gen_size;
+    if let (Some(size), Some(max_size)) = (size, max_size) {
+        if size > max_size {
+            return Err(format!(
+                "day {day_num} can't generate a size over {max_size}"
+            ));
+        }
+    }
+
+    Ok(GenerateArgs {
+        day_num,
+        seed,
+        size,
+    })
+}
+
+fn parse_new_args(args: &[&str]) -> Result<NewArgs, String> {
+    let mut args = args.iter();
+    let day_num = parse_day_num(args.next().ok_or("missing day")?)?;
+    if days::get_day(day_num).is_some() {
+        return Err(format!("day {day_num} already has a solution"));
+    }
+    if let Some(extra) = args.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    Ok(NewArgs { day_num })
+}
+
+fn parse_repl_args(args: &[&str]) -> Result<ReplArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--input" | "-i" => input = Some(next_value(&mut args, arg)?.to_string()),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_num = match parse_day_sel(positional.next().ok_or("missing day")?)? {
+        DaySel::One(num) => num,
+        DaySel::All => return Err("repl needs a single day".to_string()),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    // Commands come in on stdin, so the input can't.
+    if input.as_deref() == Some("-") {
+        return Err("repl can't read its input from stdin".to_string());
+    }
+
+    Ok(ReplArgs { day_num, input })
+}
+
+fn parse_render_args(args: &[&str]) -> Result<RenderArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+    let mut output = None;
+    let mut scale = DEFAULT_RENDER_SCALE;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--input" | "-i" => input = Some(next_value(&mut args, arg)?.to_string()),
+            "--output" | "-o" => output = Some(next_value(&mut args, arg)?.to_string()),
+            "--scale" => scale = parse_count(next_value(&mut args, arg)?, arg, 1)? as usize,
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_num = match parse_day_sel(positional.next().ok_or("missing day")?)? {
+        DaySel::One(num) => num,
+        DaySel::All => return Err("render needs a single day".to_string()),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    Ok(RenderArgs {
+        day_num,
+        input,
+        output: output.unwrap_or(format!("day{day_num:02}.png")),
+        scale,
+    })
+}
+
+fn parse_frame_format(s: &str) -> Result<FrameFormat, String> {
+    match s {
+        "txt" => Ok(FrameFormat::Text),
+        "ppm" => Ok(FrameFormat::Ppm),
+        "png" => Ok(FrameFormat::Png),
+        _ => Err(format!("bad frame format: {s}")),
+    }
+}
+
+fn parse_record_args(args: &[&str]) -> Result<RecordArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+    let mut output = None;
+    let mut format = None;
+    let mut scale = DEFAULT_RENDER_SCALE;
+    let mut start = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--input" | "-i" => input = Some(next_value(&mut args, arg)?.to_string()),
+            "--output" | "-o" => output = Some(next_value(&mut args, arg)?.to_string()),
+            "--format" => format = Some(parse_frame_format(next_value(&mut args, arg)?)?),
+            "--scale" => scale = parse_count(next_value(&mut args, arg)?, arg, 1)? as usize,
+            "--start" => start = Some(next_value(&mut args, arg)?.to_string()),
+            _ => positional.push(*arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let day_num = match parse_day_sel(positional.next().ok_or("missing day")?)? {
+        DaySel::One(num) => num,
+        DaySel::All => return Err("record needs a single day".to_string()),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {extra}"));
+    }
+
+    // Anything ending in .gif is an animation and anything else a directory
+    // to put the frames in.
+    let output = output.unwrap_or(format!("day{day_num:02}-frames"));
+    let format = if output.ends_with(".gif") {
+        if format.is_some() {
+            return Err("--format is for frame directories, not GIFs".to_string());
+        }
+        None
+    } else {
+        Some(format.unwrap_or(FrameFormat::Png))
+    };
+
+    Ok(RecordArgs {
+        day_num,
+        input,
+        output,
+        format,
+        scale,
+        start,
+    })
+}
+
+// Matches -v, -vv, -vvv and so on for `c` = 'v'.
+fn is_repeated_flag(arg: &str, c: char) -> bool {
+    arg.strip_prefix('-')
+        .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|rest_c| rest_c == c))
+}
+
+// Strips the global -v/-q flags, which go before the command so they can't be
+// mistaken for a value like `--input -v`. Each -v shows one more level of
+// solver diagnostics and each -q one fewer.
+fn parse_verbosity(args: &[String]) -> (i32, Vec<String>) {
+    let mut verbosity = 0;
+    for (idx, arg) in args.iter().enumerate() {
+        match &arg[..] {
+            "--verbose" => verbosity += 1,
+            "--quiet" => verbosity -= 1,
+            flag if is_repeated_flag(flag, 'v') => verbosity += flag.len() as i32 - 1,
+            flag if is_repeated_flag(flag, 'q') => verbosity -= flag.len() as i32 - 1,
+            _ => return (verbosity, args[idx..].to_vec()),
+        }
+    }
+    (verbosity, vec![])
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let args = args.iter().map(|arg| &arg[..]).collect::<Vec<&str>>();
+
+    match args.first() {
+        Some(&"run") => Ok(Command::Run(parse_run_args(&args[1..])?)),
+        Some(&"bench") => Ok(Command::Bench(parse_bench_args(&args[1..])?)),
+        Some(&"verify") => Ok(Command::Verify(parse_verify_args(&args[1..])?)),
+        Some(&"fetch") => Ok(Command::Fetch(parse_fetch_args(&args[1..])?)),
+        Some(&"generate") => Ok(Command::Generate(parse_generate_args(&args[1..])?)),
+        Some(&"new") => Ok(Command::New(parse_new_args(&args[1..])?)),
+        Some(&"repl") => Ok(Command::Repl(parse_repl_args(&args[1..])?)),
+        Some(&"render") => Ok(Command::Render(parse_render_args(&args[1..])?)),
+        Some(&"record") => Ok(Command::Record(parse_record_args(&args[1..])?)),
+        Some(cmd) => Err(format!("unknown command: {cmd}")),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn get_days(day_sel: DaySel) -> Vec<&'static Day> {
+    match day_sel {
+        DaySel::One(num) => vec![days::get_day(num).unwrap()],
+        DaySel::All => DAYS.iter().collect(),
+    }
+}
+
+fn get_parts(part_sel: PartSel) -> Vec<Part> {
+    match part_sel {
+        PartSel::One(part) => vec![part],
+        PartSel::All => vec![Part::A, Part::B],
+    }
+}
+
+// Solves one part within the time budget, if there is one. None means the
+// solver was cancelled when the time ran out.
+fn solve_part(
+    day: &Day,
+    parsed: &dyn Any,
+    part: Part,
+    timeout: Option<Duration>,
+) -> Option<String> {
+    let token = cancel::Token::new();
+    let _timer = timeout.map(|timeout| token.cancel_after(timeout));
+    cancel::run_with(Some(token), || day.solution.solve_any(parsed, part)).ok()
+}
+
+// Runs `f` on every day and hands each result to `done` in day order. With the
+// `parallel` feature the days run concurrently, so nothing is handed over
+// until they've all finished; without it results are handed over as they come.
+fn for_each_day<R: Send>(
+    days: &[&'static Day],
+    f: impl Fn(&Day) -> R + Sync,
+    mut done: impl FnMut(&Day, R),
+) {
+    if cfg!(feature = "parallel") {
+        let results = par::map(days, |day| f(day));
+        days.iter()
+            .zip(results)
+            .for_each(|(day, result)| done(day, result));
+    } else {
+        days.iter().for_each(|day| done(day, f(day)));
+    }
+}
+
+// What running one day has to say, held back so it can be printed in order.
+#[derive(Debug, Default)]
+struct DayReport {
+    out: Vec<String>,
+    err: Vec<String>,
+    failed: bool,
+}
+
+fn run_day(day: &Day, parts: &[Part], run_args: &RunArgs, single_job: bool) -> DayReport {
+    let mut report = DayReport::default();
+
+    let mut day_parts = vec![];
+    for part in parts {
+        if day.has_part(*part) {
+            day_parts.push(*part);
+        } else {
+            report.err.push(format!(
+                "day{:02}{}: not implemented",
+                day.num,
+                part.label()
+            ));
+            report.failed |= single_job;
+        }
+    }
+    if day_parts.is_empty() {
+        return report;
+    }
+
+    // Read and parse only once so stdin can feed both parts.
+    let source = InputSource::resolve(run_args.input.as_deref(), &day.input_filename());
+    let input = match source.try_read() {
+        Ok(input) => input,
+        Err(err) => {
+            report.err.push(format!("day{:02}: {err}", day.num));
+            report.failed = true;
+            return report;
+        }
+    };
+
+    let parsed = day.solution.parse_any(&input);
+    let input_path = source.to_string();
+
+    for part in day_parts {
+        let label = format!("day{:02}{}", day.num, part.label());
+        let start = Instant::now();
+        let result = solve_part(day, parsed.as_ref(), part, run_args.timeout);
+        let elapsed = start.elapsed();
+
+        report.failed |= result.is_none();
+        report.out.push(match (run_args.format, result) {
+            (Format::Json, result) => RunRecord {
+                day: day.num,
+                part,
+                answer: result.as_deref(),
+                elapsed,
+                input_path: &input_path,
+            }
+            .to_json(),
+            (Format::Text, result) => {
+                let result = result.unwrap_or_else(|| "TIMEOUT".to_string());
+                if single_job {
+                    result
+                } else {
+                    format!("{label}: {result}")
+                }
+            }
+        });
+    }
+
+    report
+}
+
+fn run(run_args: &RunArgs) -> ExitCode {
+    let days = get_days(run_args.day_sel);
+    let parts = get_parts(run_args.part_sel);
+    let single_job = days.len() == 1 && parts.len() == 1;
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for_each_day(
+        &days,
+        |day| run_day(day, &parts, run_args, single_job),
+        |_, report| {
+            report.err.iter().for_each(|line| eprintln!("{line}"));
+            report.out.iter().for_each(|line| println!("{line}"));
+            if report.failed {
+                exit_code = ExitCode::FAILURE;
+            }
+        },
+    );
+
+    exit_code
+}
+
+fn bench(bench_args: &BenchArgs) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut results = vec![];
+
+    // Days are timed one at a time even with the `parallel` feature, so they
+    // aren't competing with each other for cores.
+    bench::print_header();
+    for day in get_days(bench_args.day_sel) {
+        let source = InputSource::resolve(bench_args.input.as_deref(), &day.input_filename());
+        let input = match source.try_read() {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("day{:02}: {err}", day.num);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        for result in bench::bench_day(day, &input, bench_args.warmup, bench_args.iterations) {
+            bench::print_result(&result);
+            results.push(result);
+        }
+    }
+
+    let written = File::create(&bench_args.output)
+        .and_then(|file| bench::write_csv(&results, BufWriter::new(file)));
+    if let Err(err) = written {
+        eprintln!("error writing {}: {err}", bench_args.output);
+        return ExitCode::FAILURE;
+    }
+
+    exit_code
+}
+
+fn check_answer(answers: &Answers, day: &Day, part: Part, answer: &str) -> (Check, String) {
+    let label = format!("day{:02}{}", day.num, part.label());
+    match answers.get(day.num, part) {
+        Some(expected) if expected == answer => (Check::Pass, String::new()),
+        Some(expected) => (
+            Check::Fail,
+            format!("{label}: expected {expected}, got {answer}"),
+        ),
+        None => (
+            Check::New,
+            format!("{label}: no recorded answer, got {answer}"),
+        ),
+    }
+}
+
+// `--record` fills in answers that are missing, but a wrong answer is most
+// likely a regression, so it's only replaced with `--overwrite` as well.
+fn should_record(verify_args: &VerifyArgs, check: Check) -> bool {
+    match check {
+        Check::New => verify_args.record,
+        Check::Fail => verify_args.record && verify_args.overwrite,
+        _ => false,
+    }
+}
+
+// Both parts' answers, None for a part that isn't implemented and Some(None)
+// for one that ran out of time.
+fn solve_day(
+    day: &Day,
+    timeout: Option<Duration>,
+) -> Result<[Option<Option<String>>; 2], InputError> {
+    let input = InputSource::resolve(None, &day.input_filename()).try_read()?;
+    let parsed = day.solution.parse_any(&input);
+    Ok([Part::A, Part::B].map(|part| {
+        day.has_part(part)
+            .then(|| solve_part(day, parsed.as_ref(), part, timeout))
+    }))
+}
+
+fn verify(verify_args: &VerifyArgs) -> ExitCode {
+    let path = &verify_args.answers;
+    let mut answers = match fs::read_to_string(path) {
+        Ok(s) => match Answers::parse(&s) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{path}: {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => Answers::default(),
+        Err(err) => {
+            eprintln!("error reading {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Collect everything before printing so the matrix isn't interleaved with
+    // whatever the solvers print.
+    let mut rows = vec![];
+    let mut notes = vec![];
+    let mut num_recorded = 0;
+    let days = get_days(verify_args.day_sel);
+    let solve = |day: &Day| solve_day(day, verify_args.timeout);
+    for_each_day(&days, solve, |day, solved| {
+        let solved = match solved {
+            Ok(solved) => solved,
+            Err(err) => {
+                notes.push(format!("day{:02}: {err}", day.num));
+                rows.push((day.num, [Check::Error; 2]));
+                return;
+            }
+        };
+
+        let mut checks = [Check::NotImplemented; 2];
+        for (idx, (part, answer)) in [Part::A, Part::B].into_iter().zip(solved).enumerate() {
+            let Some(answer) = answer else {
+                continue;
+            };
+            let Some(answer) = answer else {
+                checks[idx] = Check::Timeout;
+                continue;
+            };
+
+            let (check, note) = check_answer(&answers, day, part, &answer);
+            if !note.is_empty() {
+                notes.push(note);
+            }
+            if should_record(verify_args, check) {
+                if let Some(expected) = answers.get(day.num, part) {
+                    notes.push(format!(
+                        "day{:02}{}: replaced {expected} with {answer}",
+                        day.num,
+                        part.label()
+                    ));
+                }
+                answers.set(day.num, part, &answer);
+                num_recorded += 1;
+            }
+            checks[idx] = check;
+        }
+        rows.push((day.num, checks));
+    });
+
+    println!("{:<6} {:<6} b", "day", "a");
+    for (day_num, [check_a, check_b]) in &rows {
+        println!(
+            "{:<6} {:<6} {}",
+            format!("day{day_num:02}"),
+            check_a.label(),
+            check_b.label()
+        );
+    }
+    notes.iter().for_each(|note| println!("{note}"));
+
+    if num_recorded > 0 {
+        if let Err(err) = fs::write(path, answers.to_toml()) {
+            eprintln!("error writing {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+        println!("recorded {num_recorded} answer(s) in {path}");
+    }
+
+    let failed = rows
+        .iter()
+        .flat_map(|(_, checks)| checks)
+        .any(|check| match check {
+            Check::Error | Check::Timeout => true,
+            Check::Fail => !should_record(verify_args, *check),
+            _ => false,
+        });
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch(fetch_args: &FetchArgs) -> ExitCode {
+    let mut config = match Config::load(&fetch_args.config) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(base_url) = &fetch_args.base_url {
+        config.base_url = base_url.clone();
+    }
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for &num in &fetch_args.day_nums {
+        let dest = match InputSource::resolve(None, &days::input_filename(num)) {
+            InputSource::File(path) => path,
+            InputSource::Stdin => unreachable!(),
+        };
+        match fetch::fetch_input(&config, num, &dest) {
+            Ok(Fetched::Cached) => println!("day{num:02}: already have {}", dest.display()),
+            Ok(Fetched::Downloaded) => println!("day{num:02}: saved {}", dest.display()),
+            Err(err) => {
+                eprintln!("day{num:02}: {err}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+// Writes to stdout so the result can be piped straight into `aoc run <day> -i -`.
+fn generate(generate_args: &GenerateArgs) -> ExitCode {
+    let day = days::get_day(generate_args.day_num).unwrap();
+    let size = generate_args.size.unwrap_or(day.default_gen_size);
+    print!("{}", (day.generate)(generate_args.seed, size));
+    ExitCode::SUCCESS
+}
+
+// Expects to be run from the workspace root, like the input paths do.
+fn new(new_args: &NewArgs) -> ExitCode {
+    match scaffold::new_day(Path::new("."), new_args.day_num) {
+        Ok(crate_dir) => {
+            println!("created {}", crate_dir.display());
+            println!("rebuild the runner to pick it up");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn repl(repl_args: &ReplArgs) -> ExitCode {
+    let day = days::get_day(repl_args.day_num).unwrap();
+    let source = InputSource::resolve(repl_args.input.as_deref(), &day.input_filename());
+    let input = match source.try_read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day{:02}: {err}", day.num);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(err) = Repl::new(day, &input).run(io::stdin().lock(), io::stdout()) {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn render(render_args: &RenderArgs) -> ExitCode {
+    let day = days::get_day(render_args.day_num).unwrap();
+    let source = InputSource::resolve(render_args.input.as_deref(), &day.input_filename());
+    let input = match source.try_read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day{:02}: {err}", day.num);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parsed = day.solution.parse_any(&input);
+    let Some(image) = day.solution.render_any(parsed.as_ref()) else {
+        eprintln!("day{:02}: nothing to render", day.num);
+        return ExitCode::FAILURE;
+    };
+
+    let output = &render_args.output;
+    if let Err(err) = image.scaled(render_args.scale).save(Path::new(output)) {
+        eprintln!("error writing {output}: {err}");
+        return ExitCode::FAILURE;
+    }
+    println!("day{:02}: saved {output}", day.num);
+    ExitCode::SUCCESS
+}
+
+fn record(record_args: &RecordArgs) -> ExitCode {
+    let day = days::get_day(record_args.day_num).unwrap();
+    let source = InputSource::resolve(record_args.input.as_deref(), &day.input_filename());
+    let input = match source.try_read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day{:02}: {err}", day.num);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let output = &record_args.output;
+    let mut recorder = match record_args.format {
+        Some(format) => Recorder::to_files(Path::new(output), format, record_args.scale),
+        None => Recorder::to_gif(Path::new(output), record_args.scale),
+    };
+
+    let parsed = day.solution.parse_any(&input);
+    let start = record_args.start.as_deref();
+    if let Err(err) = day
+        .solution
+        .record_any(parsed.as_ref(), start, &mut recorder)
+    {
+        eprintln!("day{:02}: {err}", day.num);
+        return ExitCode::FAILURE;
+    }
+
+    match recorder.finish() {
+        Ok(num_frames) => {
+            println!(
+                "day{:02}: recorded {num_frames} frame(s) in {output}",
+                day.num
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error writing {output}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let (verbosity, args) = parse_verbosity(&args);
+    log::set_max_level(Level::from_verbosity(verbosity));
+
+    match parse_args(&args) {
+        Ok(Command::Run(run_args)) => run(&run_args),
+        Ok(Command::Bench(bench_args)) => bench(&bench_args),
+        Ok(Command::Verify(verify_args)) => verify(&verify_args),
+        Ok(Command::Fetch(fetch_args)) => fetch(&fetch_args),
+        Ok(Command::Generate(generate_args)) => generate(&generate_args),
+        Ok(Command::New(new_args)) => new(&new_args),
+        Ok(Command::Repl(repl_args)) => repl(&repl_args),
+        Ok(Command::Render(render_args)) => render(&render_args),
+        Ok(Command::Record(record_args)) => record(&record_args),
+        Err(msg) => {
+            eprintln!("{msg}");
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(s: &str) -> Vec<String> {
+        s.split(' ').map(|arg| arg.to_string()).collect()
+    }
+
+    fn run_cmd(day_sel: DaySel, part_sel: PartSel, input: Option<&str>) -> Command {
+        Command::Run(RunArgs {
+            day_sel,
+            part_sel,
+            input: input.map(|s| s.to_string()),
+            format: Format::Text,
+            timeout: None,
+        })
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&to_args("run 16 b")),
+            Ok(run_cmd(DaySel::One(16), PartSel::One(Part::B), None))
+        );
+        assert_eq!(
+            parse_args(&to_args("run 05")),
+            Ok(run_cmd(DaySel::One(5), PartSel::All, None))
+        );
+        assert_eq!(
+            parse_args(&to_args("run all")),
+            Ok(run_cmd(DaySel::All, PartSel::All, None))
+        );
+        assert_eq!(
+            parse_args(&to_args("run all a")),
+            Ok(run_cmd(DaySel::All, PartSel::One(Part::A), None))
+        );
+        assert!(parse_args(&to_args("run 26")).is_err());
+        assert!(parse_args(&to_args("run 1 c")).is_err());
+        assert!(parse_args(&to_args("run 1 a b")).is_err());
+        assert!(parse_args(&to_args("go 1")).is_err());
+        assert!(parse_args(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_input() {
+        assert_eq!(
+            parse_args(&to_args("run 5 a --input my/day05.txt")),
+            Ok(run_cmd(
+                DaySel::One(5),
+                PartSel::One(Part::A),
+                Some("my/day05.txt")
+            ))
+        );
+        assert_eq!(
+            parse_args(&to_args("run -i - 5")),
+            Ok(run_cmd(DaySel::One(5), PartSel::All, Some("-")))
+        );
+        assert!(parse_args(&to_args("run 5 --input")).is_err());
+        assert!(parse_args(&to_args("run all --input my/day05.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_format() {
+        assert_eq!(
+            parse_args(&to_args("run all --format json")),
+            Ok(Command::Run(RunArgs {
+                day_sel: DaySel::All,
+                part_sel: PartSel::All,
+                input: None,
+                format: Format::Json,
+                timeout: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("run 3 b --format text")),
+            Ok(run_cmd(DaySel::One(3), PartSel::One(Part::B), None))
+        );
+        assert!(parse_args(&to_args("run 3 --format xml")).is_err());
+        assert!(parse_args(&to_args("run 3 --format")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_timeout() {
+        assert_eq!(
+            parse_args(&to_args("run all --timeout 5")),
+            Ok(Command::Run(RunArgs {
+                day_sel: DaySel::All,
+                part_sel: PartSel::All,
+                input: None,
+                format: Format::Text,
+                timeout: Some(Duration::from_secs(5)),
+            }))
+        );
+        assert!(parse_args(&to_args("run all --timeout 0")).is_err());
+        assert!(parse_args(&to_args("run all --timeout 1.5")).is_err());
+        assert!(parse_args(&to_args("verify --timeout")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        assert_eq!(parse_verbosity(&to_args("run 5")), (0, to_args("run 5")));
+        assert_eq!(
+            parse_verbosity(&to_args("-v -vv run 5")),
+            (3, to_args("run 5"))
+        );
+        assert_eq!(
+            parse_verbosity(&to_args("-q --verbose --quiet -qq verify")),
+            (-3, to_args("verify"))
+        );
+        assert_eq!(
+            parse_verbosity(&to_args("-v run 5 --input -v")),
+            (1, to_args("run 5 --input -v"))
+        );
+        assert_eq!(
+            parse_verbosity(&to_args("verify -q")),
+            (0, to_args("verify -q"))
+        );
+        assert_eq!(parse_verbosity(&to_args("-vv")), (2, vec![]));
+        assert_eq!(
+            parse_verbosity(&to_args("run 5 -i - -vq")),
+            (0, to_args("run 5 -i - -vq"))
+        );
+        assert_eq!(parse_verbosity(&to_args("run vv")), (0, to_args("run vv")));
+    }
+
+    #[test]
+    fn test_parse_args_bench() {
+        assert_eq!(
+            parse_args(&to_args("bench 12")),
+            Ok(Command::Bench(BenchArgs {
+                day_sel: DaySel::One(12),
+                input: None,
+                iterations: DEFAULT_BENCH_ITERATIONS,
+                warmup: DEFAULT_BENCH_WARMUP,
+                output: DEFAULT_BENCH_OUTPUT.to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("bench all -n 50 --warmup 0 -o out.csv")),
+            Ok(Command::Bench(BenchArgs {
+                day_sel: DaySel::All,
+                input: None,
+                iterations: 50,
+                warmup: 0,
+                output: "out.csv".to_string(),
+            }))
+        );
+        assert!(parse_args(&to_args("bench 12 -n 0")).is_err());
+        assert!(parse_args(&to_args("bench 12 --warmup x")).is_err());
+        assert!(parse_args(&to_args("bench 12 a")).is_err());
+        assert!(parse_args(&to_args("bench all -i my/day12.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_verify() {
+        assert_eq!(
+            parse_args(&to_args("verify")),
+            Ok(Command::Verify(VerifyArgs {
+                day_sel: DaySel::All,
+                record: false,
+                overwrite: false,
+                answers: DEFAULT_ANSWERS_PATH.to_string(),
+                timeout: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("verify 7 --record --answers my.toml --timeout 30")),
+            Ok(Command::Verify(VerifyArgs {
+                day_sel: DaySel::One(7),
+                record: true,
+                overwrite: false,
+                answers: "my.toml".to_string(),
+                timeout: Some(Duration::from_secs(30)),
+            }))
+        );
+        assert!(matches!(
+            parse_args(&to_args("verify --record --overwrite")),
+            Ok(Command::Verify(VerifyArgs {
+                record: true,
+                overwrite: true,
+                ..
+            }))
+        ));
+        assert!(parse_args(&to_args("verify --overwrite")).is_err());
+        assert!(parse_args(&to_args("verify 7 a")).is_err());
+        assert!(parse_args(&to_args("verify --answers")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_fetch() {
+        assert_eq!(
+            parse_args(&to_args("fetch 25")),
+            Ok(Command::Fetch(FetchArgs {
+                day_nums: vec![25],
+                config: DEFAULT_CONFIG_PATH.to_string(),
+                base_url: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args(
+                "fetch all --config my.toml --base-url http://localhost:8080"
+            )),
+            Ok(Command::Fetch(FetchArgs {
+                day_nums: DAYS.iter().map(|day| day.num).collect(),
+                config: "my.toml".to_string(),
+                base_url: Some("http://localhost:8080".to_string()),
+            }))
+        );
+        assert!(parse_args(&to_args("fetch 26")).is_err());
+        assert!(parse_args(&to_args("fetch 0")).is_err());
+        assert!(parse_args(&to_args("fetch 3 4")).is_err());
+        assert!(parse_args(&to_args("fetch 3 --config")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_generate() {
+        assert_eq!(
+            parse_args(&to_args("generate 13")),
+            Ok(Command::Generate(GenerateArgs {
+                day_num: 13,
+                seed: 0,
+                size: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("generate 5 --seed 42 --size 10")),
+            Ok(Command::Generate(GenerateArgs {
+                day_num: 5,
+                seed: 42,
+                size: Some(10),
+            }))
+        );
+        assert!(parse_args(&to_args("generate all")).is_err());
+        assert!(parse_args(&to_args("generate 26")).is_err());
+        assert!(parse_args(&to_args("generate 5 --seed -1")).is_err());
+        assert!(parse_args(&to_args("generate 5 --size 0")).is_err());
+        assert!(parse_args(&to_args("generate 6 --size 4")).is_ok());
+        assert!(parse_args(&to_args("generate 6 --size 5")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_new() {
+        let free = (1..=MAX_DAY).find(|num| days::get_day(*num).is_none());
+        if let Some(free) = free {
+            assert_eq!(
+                parse_args(&to_args(&format!("new {free}"))),
+                Ok(Command::New(NewArgs { day_num: free }))
+            );
+            assert!(parse_args(&to_args(&format!("new {free} 1"))).is_err());
+        }
+        assert!(parse_args(&to_args("new 1")).is_err());
+        assert!(parse_args(&to_args("new 26")).is_err());
+        assert!(parse_args(&to_args("new")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_repl() {
+        assert_eq!(
+            parse_args(&to_args("repl 13")),
+            Ok(Command::Repl(ReplArgs {
+                day_num: 13,
+                input: None
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("repl 13 -i sample.txt")),
+            Ok(Command::Repl(ReplArgs {
+                day_num: 13,
+                input: Some("sample.txt".to_string())
+            }))
+        );
+        assert!(parse_args(&to_args("repl 13 -i -")).is_err());
+        assert!(parse_args(&to_args("repl all")).is_err());
+        assert!(parse_args(&to_args("repl 13 a")).is_err());
+        assert!(parse_args(&to_args("repl")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_render() {
+        assert_eq!(
+            parse_args(&to_args("render 17")),
+            Ok(Command::Render(RenderArgs {
+                day_num: 17,
+                input: None,
+                output: "day17.png".to_string(),
+                scale: DEFAULT_RENDER_SCALE,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("render 10 -i - -o loop.ppm --scale 1")),
+            Ok(Command::Render(RenderArgs {
+                day_num: 10,
+                input: Some("-".to_string()),
+                output: "loop.ppm".to_string(),
+                scale: 1,
+            }))
+        );
+        assert!(parse_args(&to_args("render 10 --scale 0")).is_err());
+        assert!(parse_args(&to_args("render all")).is_err());
+        assert!(parse_args(&to_args("render")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_record() {
+        assert_eq!(
+            parse_args(&to_args("record 16")),
+            Ok(Command::Record(RecordArgs {
+                day_num: 16,
+                input: None,
+                output: "day16-frames".to_string(),
+                format: Some(FrameFormat::Png),
+                scale: DEFAULT_RENDER_SCALE,
+                start: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args(
+                "record 16 --start 0,3,down -o beams.gif --scale 2"
+            )),
+            Ok(Command::Record(RecordArgs {
+                day_num: 16,
+                input: None,
+                output: "beams.gif".to_string(),
+                format: None,
+                scale: 2,
+                start: Some("0,3,down".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("record 14 --format txt")),
+            Ok(Command::Record(RecordArgs {
+                day_num: 14,
+                input: None,
+                output: "day14-frames".to_string(),
+                format: Some(FrameFormat::Text),
+                scale: DEFAULT_RENDER_SCALE,
+                start: None,
+            }))
+        );
+        assert!(parse_args(&to_args("record 14 -o x.gif --format txt")).is_err());
+        assert!(parse_args(&to_args("record 14 --format gif")).is_err());
+        assert!(parse_args(&to_args("record all")).is_err());
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let day = days::get_day(7).unwrap();
+        let mut answers = Answers::default();
+        answers.set(7, Part::A, "6440");
+
+        assert_eq!(check_answer(&answers, day, Part::A, "6440").0, Check::Pass);
+        assert_eq!(
+            check_answer(&answers, day, Part::A, "6441"),
+            (Check::Fail, "day07a: expected 6440, got 6441".to_string())
+        );
+        assert_eq!(check_answer(&answers, day, Part::B, "5905").0, Check::New);
+    }
+
+    #[test]
+    fn test_should_record() {
+        let args = |args: &str| match parse_args(&to_args(args)) {
+            Ok(Command::Verify(verify_args)) => verify_args,
+            other => panic!("not verify args: {other:?}"),
+        };
+        let records = |verify_args: &VerifyArgs| {
+            [Check::Pass, Check::New, Check::Fail, Check::Timeout]
+                .map(|check| should_record(verify_args, check))
+        };
+
+        assert_eq!(records(&args("verify")), [false; 4]);
+        assert_eq!(
+            records(&args("verify --record")),
+            [false, true, false, false]
+        );
+        assert_eq!(
+            records(&args("verify --record --overwrite")),
+            [false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_solve_part_timeout() {
+        // ZZZ can't be reached, so part 1 would never finish on its own.
+        let day = days::get_day(8).unwrap();
+        let parsed = day
+            .solution
+            .parse_any("L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n");
+        let timeout = Some(Duration::from_millis(50));
+        assert_eq!(solve_part(day, parsed.as_ref(), Part::A, timeout), None);
+
+        let parsed = day
+            .solution
+            .parse_any("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n");
+        assert_eq!(
+            solve_part(day, parsed.as_ref(), Part::A, timeout),
+            Some("1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_days_and_parts() {
+        let days = get_days(DaySel::One(3))
+            .iter()
+            .map(|day| day.num)
+            .collect::<Vec<u32>>();
+        assert_eq!(days, vec![3]);
+        assert_eq!(get_days(DaySel::All).len(), DAYS.len());
+
+        assert_eq!(get_parts(PartSel::All), vec![Part::A, Part::B]);
+        assert_eq!(get_parts(PartSel::One(Part::B)), vec![Part::B]);
+    }
+}
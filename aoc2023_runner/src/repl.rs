@@ -0,0 +1,236 @@
+use crate::bench::fmt_duration;
+use crate::days::Day;
+use aoc2023_utils::Part;
+use std::any::Any;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+const HELP: &str = concat!(
+    "part1, part2          solve a part\n",
+    "show <n>              print the nth parsed item, counting from 1\n",
+    "param                 list the day's params\n",
+    "param <name> <value>  solve with a param set to value\n",
+    "time <command>        run a command and print how long it took\n",
+    "help                  print this\n",
+    "quit                  leave",
+);
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Solve(Part),
+    Show(usize),
+    Params,
+    Param(String, u32),
+    Time(Box<Command>),
+    Help,
+    Quit,
+}
+
+fn parse_command(words: &[&str]) -> Result<Command, String> {
+    let Some((&name, args)) = words.split_first() else {
+        return Err("missing command".to_string());
+    };
+
+    let num_args = match name {
+        "time" => return Ok(Command::Time(Box::new(parse_command(args)?))),
+        "show" => 1,
+        "param" if !args.is_empty() => 2,
+        _ => 0,
+    };
+    if args.len() != num_args {
+        return Err(format!("{name} takes {num_args} argument(s), try help"));
+    }
+
+    match name {
+        "part1" => Ok(Command::Solve(Part::A)),
+        "part2" => Ok(Command::Solve(Part::B)),
+        "show" => args[0]
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n >= 1)
+            .map(Command::Show)
+            .ok_or(format!("bad item number: {}", args[0])),
+        "param" if args.is_empty() => Ok(Command::Params),
+        "param" => args[1]
+            .parse::<u32>()
+            .map(|value| Command::Param(args[0].to_string(), value))
+            .map_err(|_| format!("bad value for {}: {}", args[0], args[1])),
+        "help" => Ok(Command::Help),
+        "quit" | "exit" => Ok(Command::Quit),
+        _ => Err(format!("unknown command: {name}, try help")),
+    }
+}
+
+// Holds on to one day's parsed input so commands can be run against it again
+// and again without re-reading or re-parsing it.
+pub struct Repl<'a> {
+    day: &'a Day,
+    parsed: Box<dyn Any>,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(day: &'a Day, input: &str) -> Self {
+        Self {
+            day,
+            parsed: day.solution.parse_any(input),
+        }
+    }
+
+    fn execute(&self, command: &Command) -> Result<String, String> {
+        let solution = self.day.solution;
+        let parsed = self.parsed.as_ref();
+        match command {
+            Command::Solve(part) if !self.day.has_part(*part) => {
+                Err(format!("part {} isn't implemented", part.label()))
+            }
+            Command::Solve(part) => catch_panic(|| solution.solve_any(parsed, *part)),
+            Command::Show(n) => catch_panic(|| solution.show_item_any(parsed, n - 1))?
+                .ok_or(format!("no item {n} to show")),
+            Command::Params if solution.params().is_empty() => {
+                Ok(format!("day{:02} has no params", self.day.num))
+            }
+            Command::Params => Ok(solution.params().join("\n")),
+            Command::Param(name, _) if !solution.params().contains(&&name[..]) => {
+                Err(format!("unknown param: {name}, try param"))
+            }
+            Command::Param(name, value) => {
+                catch_panic(|| solution.solve_with_param_any(parsed, name, *value))
+            }
+            Command::Time(command) => {
+                let start = Instant::now();
+                let result = self.execute(command);
+                let took = format!("took {}", fmt_duration(start.elapsed()));
+                result
+                    .map(|out| format!("{out}\n{took}"))
+                    .map_err(|err| format!("{err}\n{took}"))
+            }
+            Command::Help => Ok(HELP.to_string()),
+            Command::Quit => Ok(String::new()),
+        }
+    }
+
+    // Reads commands until quit or the end of the input. A command that fails
+    // or panics doesn't end the session.
+    pub fn run(&self, commands: impl BufRead, mut out: impl Write) -> io::Result<()> {
+        let prompt = format!("day{:02}> ", self.day.num);
+        write!(out, "{prompt}")?;
+        out.flush()?;
+
+        for line in commands.lines() {
+            let line = line?;
+            let words = line.split_whitespace().collect::<Vec<&str>>();
+            if !words.is_empty() {
+                match parse_command(&words).and_then(|command| match command {
+                    Command::Quit => Ok(None),
+                    command => self.execute(&command).map(Some),
+                }) {
+                    Ok(None) => return Ok(()),
+                    Ok(Some(result)) => writeln!(out, "{result}")?,
+                    Err(err) => writeln!(out, "error: {err}")?,
+                }
+            }
+            write!(out, "{prompt}")?;
+            out.flush()?;
+        }
+
+        writeln!(out)
+    }
+}
+
+// The panic hook has already printed what went wrong by the time this returns.
+fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| "panicked".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    const SAMPLE_INPUT: &str = concat!(
+        "...#......\n",
+        ".......#..\n",
+        "#.........\n",
+        "..........\n",
+        "......#...\n",
+        ".#........\n",
+        ".........#\n",
+        "..........\n",
+        ".......#..\n",
+        "#...#.....\n",
+    );
+
+    fn parse(line: &str) -> Result<Command, String> {
+        parse_command(&line.split_whitespace().collect::<Vec<&str>>())
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse("part1"), Ok(Command::Solve(Part::A)));
+        assert_eq!(parse("part2"), Ok(Command::Solve(Part::B)));
+        assert_eq!(parse("show 3"), Ok(Command::Show(3)));
+        assert_eq!(parse("param"), Ok(Command::Params));
+        assert_eq!(
+            parse("param expansion_factor 10"),
+            Ok(Command::Param("expansion_factor".to_string(), 10))
+        );
+        assert_eq!(
+            parse("time time part2"),
+            Ok(Command::Time(Box::new(Command::Time(Box::new(
+                Command::Solve(Part::B)
+            )))))
+        );
+        assert_eq!(parse("quit"), Ok(Command::Quit));
+
+        assert!(parse("show").is_err());
+        assert!(parse("show 0").is_err());
+        assert!(parse("show x").is_err());
+        assert!(parse("param expansion_factor").is_err());
+        assert!(parse("param expansion_factor -1").is_err());
+        assert!(parse("part1 now").is_err());
+        assert!(parse("time").is_err());
+        assert!(parse("part3").is_err());
+    }
+
+    #[test]
+    fn test_execute() {
+        let repl = Repl::new(days::get_day(11).unwrap(), SAMPLE_INPUT);
+        let execute = |line| repl.execute(&parse(line).unwrap());
+
+        assert_eq!(execute("part1"), Ok("374".to_string()));
+        assert_eq!(execute("param expansion_factor 10"), Ok("1030".to_string()));
+        assert_eq!(
+            execute("param expansion_factor 100"),
+            Ok("8410".to_string())
+        );
+        assert_eq!(execute("param"), Ok("expansion_factor".to_string()));
+        assert!(execute("param nope 1").is_err());
+        assert_eq!(
+            execute("show 2"),
+            Ok("Coord { row: 1, col: 7 }".to_string())
+        );
+        assert!(execute("show 9").is_ok());
+        assert!(execute("show 10").is_err());
+
+        let timed = execute("time part1").unwrap();
+        assert!(timed.starts_with("374\ntook "));
+    }
+
+    #[test]
+    fn test_run() {
+        let repl = Repl::new(days::get_day(11).unwrap(), SAMPLE_INPUT);
+        let mut out = vec![];
+        repl.run(&b"part1\n\nbogus\npart1\nquit\npart1\n"[..], &mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "day11> 374\n",
+                "day11> day11> error: unknown command: bogus, try help\n",
+                "day11> 374\n",
+                "day11> ",
+            )
+        );
+    }
+}
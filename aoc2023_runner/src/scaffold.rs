@@ -0,0 +1,445 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Templates for a new day's files. Every `%NN%` becomes the two digit day
+// number.
+const CARGO_TOML: &str = r#"[package]
+name = "day%NN%a"
+version = "0.1.0"
+edition = "2021"
+rust-version = "1.74"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[lib]
+name = "day%NN%"
+path = "src/lib.rs"
+
+[dependencies]
+aoc2023_utils = { path = "../aoc2023_utils" }
+
+[build-dependencies]
+aoc2023_utils = { path = "../aoc2023_utils" }
+"#;
+
+const BUILD_RS: &str = r#"fn main() {
+    aoc2023_utils::fixtures::generate_tests();
+}
+"#;
+
+// Whatever the day makes `pub` should be added to the `pub use` as well.
+const LIB_RS: &str = r#"mod day%NN%;
+pub mod generate;
+
+pub use day%NN%::Day%NN%;
+"#;
+
+const DAY_RS: &str = r#"use aoc2023_utils::Solution;
+
+fn parse_input(s: &str) -> Vec<String> {
+    s.lines().map(ToString::to_string).collect()
+}
+
+pub struct Day%NN%;
+
+impl Solution for Day%NN% {
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const HAS_PART1: bool = false;
+    const HAS_PART2: bool = false;
+
+    fn parse(&self, input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
+}
+
+aoc2023_utils::fixture_tests!(Day%NN%);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = concat!("first line\n", "second line\n");
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(SAMPLE_INPUT).len(), 2);
+    }
+}
+"#;
+
+const GENERATE_RS: &str = r#"use aoc2023_utils::Rng;
+
+// Number of lines.
+pub const DEFAULT_SIZE: usize = 100;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size).map(|_| format!("{}\n", rng.next_u64())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(1, 10), generate(1, 10));
+        assert_ne!(generate(1, 10), generate(2, 10));
+    }
+}
+"#;
+
+const SAMPLE_EXPECTED: &str = r#"# Answers for sample.txt, e.g.
+# a = 142
+# b = 281
+"#;
+
+fn fill(template: &str, num: u32) -> String {
+    template.replace("%NN%", &format!("{num:02}"))
+}
+
+pub fn crate_dir_name(num: u32) -> String {
+    format!("aoc2023_day{num:02}")
+}
+
+// Every file in a new day's crate, relative to the crate's directory.
+fn crate_files(num: u32) -> Vec<(PathBuf, String)> {
+    let src = Path::new("src");
+    let examples = Path::new("examples");
+    vec![
+        (PathBuf::from("Cargo.toml"), fill(CARGO_TOML, num)),
+        (PathBuf::from("build.rs"), BUILD_RS.to_string()),
+        (src.join("lib.rs"), fill(LIB_RS, num)),
+        (src.join(format!("day{num:02}.rs")), fill(DAY_RS, num)),
+        (src.join("generate.rs"), GENERATE_RS.to_string()),
+        (examples.join("sample.txt"), String::new()),
+        (
+            examples.join("sample.expected"),
+            SAMPLE_EXPECTED.to_string(),
+        ),
+    ]
+}
+
+// Puts `new_line` among the lines that `key` picks out, in key order.
+// `after_last` is the line to put it after when there are no such lines yet.
+fn insert_sorted_line(
+    s: &str,
+    new_line: &str,
+    key: impl Fn(&str) -> Option<u32>,
+    new_key: u32,
+    after_last: &str,
+) -> Result<String, String> {
+    let mut lines = s.lines().collect::<Vec<&str>>();
+    let keys = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| key(line).map(|line_key| (idx, line_key)))
+        .collect::<Vec<(usize, u32)>>();
+    if keys.iter().any(|(_, line_key)| *line_key == new_key) {
+        return Err(format!("already has an entry for day {new_key}"));
+    }
+
+    let idx = match keys.iter().find(|(_, line_key)| *line_key > new_key) {
+        Some((idx, _)) => *idx,
+        None => match keys.last() {
+            Some((idx, _)) => idx + 1,
+            None => {
+                lines
+                    .iter()
+                    .position(|line| line.trim() == after_last)
+                    .ok_or(format!("couldn't find `{after_last}`"))?
+                    + 1
+            }
+        },
+    };
+    lines.insert(idx, new_line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn day_num_between(line: &str, prefix: &str, suffix: &str) -> Option<u32> {
+    line.trim()
+        .strip_prefix(prefix)?
+        .split_once(suffix)?
+        .0
+        .parse()
+        .ok()
+}
+
+fn add_workspace_member(cargo_toml: &str, num: u32) -> Result<String, String> {
+    insert_sorted_line(
+        cargo_toml,
+        &format!("    \"{}\",", crate_dir_name(num)),
+        |line| day_num_between(line, "\"aoc2023_day", "\""),
+        num,
+        "members = [",
+    )
+}
+
+fn add_runner_dependency(cargo_toml: &str, num: u32) -> Result<String, String> {
+    insert_sorted_line(
+        cargo_toml,
+        &format!("day{num:02}a = {{ path = \"../{}\" }}", crate_dir_name(num)),
+        |line| day_num_between(line, "day", "a = "),
+        num,
+        "[dependencies]",
+    )
+}
+
+// DAYS entries span several lines, so this goes by the `num: N,` lines and
+// then backs up to the `Day {` opening the entry.
+fn add_days_entry(days_rs: &str, num: u32) -> Result<String, String> {
+    let entry = format!(
+        concat!(
+            "    Day {{\n",
+            "        num: {num},\n",
+            "        solution: &day{num:02}::Day{num:02},\n",
+            "        generate: day{num:02}::generate::generate,\n",
+            "        default_gen_size: day{num:02}::generate::DEFAULT_SIZE,\n",
+            "        max_gen_size: None,\n",
+            "    }},",
+        ),
+        num = num
+    );
+
+    let lines = days_rs.lines().collect::<Vec<&str>>();
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or("couldn't find DAYS")?;
+    let days_end = days_start
+        + lines[days_start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or("couldn't find the end of DAYS")?;
+    let nums = (days_start..days_end)
+        .filter_map(|idx| day_num_between(lines[idx], "num: ", ",").map(|num| (idx, num)))
+        .collect::<Vec<(usize, u32)>>();
+    if nums.iter().any(|(_, entry_num)| *entry_num == num) {
+        return Err(format!("DAYS already has day {num}"));
+    }
+
+    let idx = match nums.iter().find(|(_, entry_num)| *entry_num > num) {
+        Some((idx, _)) => idx - 1,
+        None => days_end,
+    };
+    let mut lines = lines;
+    lines.insert(idx, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Creates the crate for a new day under `root` and registers it with the
+// workspace and the runner, returning the crate's directory.
+pub fn new_day(root: &Path, num: u32) -> Result<PathBuf, String> {
+    let crate_dir = root.join(crate_dir_name(num));
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("error reading {}: {err}", path.display()))
+    };
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|err| format!("error writing {}: {err}", path.display()))
+    };
+
+    // Work out every edit before touching anything, so a tree the edits don't
+    // apply to is left as it was.
+    let workspace_toml = root.join("Cargo.toml");
+    let runner_toml = root.join("aoc2023_runner").join("Cargo.toml");
+    let days_rs = root.join("aoc2023_runner").join("src").join("days.rs");
+    let edits = [
+        (
+            &workspace_toml,
+            add_workspace_member(&read(&workspace_toml)?, num),
+        ),
+        (
+            &runner_toml,
+            add_runner_dependency(&read(&runner_toml)?, num),
+        ),
+        (&days_rs, add_days_entry(&read(&days_rs)?, num)),
+    ];
+    let mut edited = vec![];
+    for (path, contents) in edits {
+        edited.push((
+            path,
+            contents.map_err(|err| format!("{}: {err}", path.display()))?,
+        ));
+    }
+
+    for (path, contents) in crate_files(num) {
+        let path = crate_dir.join(path);
+        fs::create_dir_all(path.parent().unwrap())
+            .map_err(|err| format!("error creating {}: {err}", crate_dir.display()))?;
+        write(&path, &contents)?;
+    }
+    for (path, contents) in edited {
+        write(path, &contents)?;
+    }
+
+    Ok(crate_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE_TOML: &str = concat!(
+        "[workspace]\n",
+        "\n",
+        "members = [\n",
+        "    \"aoc2023_utils\",\n",
+        "    \"aoc2023_day01\",\n",
+        "    \"aoc2023_day03\",\n",
+        "    \"aoc2023_runner\",\n",
+        "]\n",
+    );
+
+    const RUNNER_TOML: &str = concat!(
+        "[dependencies]\n",
+        "\n",
+        "aoc2023_utils = { path = \"../aoc2023_utils\" }\n",
+        "day01a = { path = \"../aoc2023_day01\" }\n",
+        "day03a = { path = \"../aoc2023_day03\" }\n",
+        "\n",
+        "[features]\n",
+    );
+
+    const DAYS_RS: &str = concat!(
+        "pub const DAYS: &[Day] = &[\n",
+        "    Day {\n",
+        "        num: 1,\n",
+        "        solution: &day01::Day01,\n",
+        "    },\n",
+        "    Day {\n",
+        "        num: 3,\n",
+        "        solution: &day03::Day03,\n",
+        "    },\n",
+        "];\n",
+        "\n",
+        "pub fn get_day(num: u32) -> Option<&'static Day> {\n",
+        "}\n",
+    );
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_new_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("aoc2023_runner").join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), WORKSPACE_TOML).unwrap();
+        fs::write(dir.join("aoc2023_runner").join("Cargo.toml"), RUNNER_TOML).unwrap();
+        fs::write(
+            dir.join("aoc2023_runner").join("src").join("days.rs"),
+            DAYS_RS,
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_add_workspace_member() {
+        let added = add_workspace_member(WORKSPACE_TOML, 2).unwrap();
+        assert!(
+            added.contains("\"aoc2023_day01\",\n    \"aoc2023_day02\",\n    \"aoc2023_day03\",")
+        );
+        let added = add_workspace_member(WORKSPACE_TOML, 18).unwrap();
+        assert!(
+            added.contains("\"aoc2023_day03\",\n    \"aoc2023_day18\",\n    \"aoc2023_runner\",")
+        );
+        assert!(add_workspace_member(WORKSPACE_TOML, 3).is_err());
+
+        let empty = "[workspace]\nmembers = [\n    \"aoc2023_utils\",\n]\n";
+        assert_eq!(
+            add_workspace_member(empty, 1).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc2023_day01\",\n    \"aoc2023_utils\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_add_runner_dependency() {
+        let added = add_runner_dependency(RUNNER_TOML, 25).unwrap();
+        assert!(added.contains(concat!(
+            "day03a = { path = \"../aoc2023_day03\" }\n",
+            "day25a = { path = \"../aoc2023_day25\" }\n",
+            "\n",
+        )));
+        assert!(add_runner_dependency(RUNNER_TOML, 1).is_err());
+    }
+
+    #[test]
+    fn test_add_days_entry() {
+        let added = add_days_entry(DAYS_RS, 2).unwrap();
+        assert!(added.contains(concat!(
+            "        solution: &day01::Day01,\n",
+            "    },\n",
+            "    Day {\n",
+            "        num: 2,\n",
+            "        solution: &day02::Day02,\n",
+            "        generate: day02::generate::generate,\n",
+            "        default_gen_size: day02::generate::DEFAULT_SIZE,\n",
+            "        max_gen_size: None,\n",
+            "    },\n",
+            "    Day {\n",
+            "        num: 3,\n",
+        )));
+        let added = add_days_entry(DAYS_RS, 4).unwrap();
+        assert!(added.contains("        max_gen_size: None,\n    },\n];\n"));
+        assert!(add_days_entry(DAYS_RS, 3).is_err());
+        assert!(add_days_entry("", 3).is_err());
+    }
+
+    #[test]
+    fn test_crate_files() {
+        let files = crate_files(7);
+        let day_rs = &files
+            .iter()
+            .find(|(path, _)| path == Path::new("src/day07.rs"))
+            .unwrap()
+            .1;
+        assert!(day_rs.contains("pub struct Day07;"));
+        assert!(day_rs.contains("fixture_tests!(Day07);"));
+        assert!(!day_rs.contains("%NN%"));
+        let lib_rs = &files
+            .iter()
+            .find(|(path, _)| path == Path::new("src/lib.rs"))
+            .unwrap()
+            .1;
+        assert!(lib_rs.contains("mod day07;"));
+        assert!(lib_rs.contains("pub use day07::Day07;"));
+        assert!(files[0].1.contains("name = \"day07a\""));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = temp_dir("day");
+        let crate_dir = new_day(&root, 2).unwrap();
+        assert_eq!(crate_dir, root.join("aoc2023_day02"));
+        assert!(crate_dir.join("src").join("day02.rs").is_file());
+        assert!(crate_dir.join("examples").join("sample.expected").is_file());
+        let workspace = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(workspace.contains("\"aoc2023_day02\""));
+
+        // Already there.
+        assert!(new_day(&root, 2).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_day_leaves_tree_alone_on_error() {
+        let root = temp_dir("error");
+        fs::write(
+            root.join("aoc2023_runner").join("src").join("days.rs"),
+            "no days here\n",
+        )
+        .unwrap();
+
+        assert!(new_day(&root, 2).is_err());
+        assert!(!root.join("aoc2023_day02").exists());
+        let workspace = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert_eq!(workspace, WORKSPACE_TOML);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Cooperative cancellation. The runner installs a token for the thread a
+// solver runs on and solvers call `check()` in loops that could run for a long
+// time. Once the token is cancelled, the next `check()` unwinds back out to
+// `run_with()`, so solvers don't need a way to return "no answer".
+#[derive(Debug, Clone, Default)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+}
+
+// The unwind payload for a cancelled solver.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Cancelled;
+
+// Cancels its token when the time runs out, unless it's dropped first.
+pub struct Timer {
+    _stop: Sender<()>,
+}
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel_after(&self, timeout: Duration) -> Timer {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let token = self.clone();
+        thread::spawn(move || {
+            if stopped.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                token.cancel();
+            }
+        });
+        Timer { _stop: stop }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+pub fn current() -> Option<Token> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+// Runs `f` with `token` as this thread's current token. Anything else that
+// unwinds out of `f` keeps unwinding.
+pub fn run_with<R>(token: Option<Token>, f: impl FnOnce() -> R) -> Result<R, Cancelled> {
+    let prev = CURRENT.with(|current| current.replace(token));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CURRENT.with(|current| current.replace(prev));
+
+    result.map_err(|payload| match payload.downcast::<Cancelled>() {
+        Ok(cancelled) => *cancelled,
+        Err(payload) => panic::resume_unwind(payload),
+    })
+}
+
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().is_some_and(Token::is_cancelled))
+}
+
+// resume_unwind rather than panic so the panic hook doesn't print anything.
+pub fn check() {
+    if is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_run_with() {
+        assert_eq!(run_with(None, || 5), Ok(5));
+        assert_eq!(run_with(Some(Token::new()), || 5), Ok(5));
+
+        let token = Token::new();
+        token.cancel();
+        assert_eq!(
+            run_with(Some(token), || {
+                check();
+                5
+            }),
+            Err(Cancelled)
+        );
+
+        // No token means nothing to cancel.
+        check();
+        assert!(current().is_none());
+    }
+
+    #[test]
+    fn test_run_with_nested() {
+        let outer = Token::new();
+        let result = run_with(Some(outer.clone()), || {
+            let inner = Token::new();
+            inner.cancel();
+            assert_eq!(run_with(Some(inner), check), Err(Cancelled));
+            assert!(!is_cancelled());
+            outer.cancel();
+            check();
+        });
+        assert_eq!(result, Err(Cancelled));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a cancellation")]
+    fn test_run_with_other_panic() {
+        let _ = run_with(Some(Token::new()), || panic!("not a cancellation"));
+    }
+
+    #[test]
+    fn test_cancel_after() {
+        let token = Token::new();
+        let _timer = token.cancel_after(Duration::from_millis(10));
+        let start = Instant::now();
+        let result = run_with(Some(token), || loop {
+            check();
+            assert!(start.elapsed() < Duration::from_secs(10));
+        });
+        assert_eq!(result, Err(Cancelled));
+
+        let token = Token::new();
+        drop(token.cancel_after(Duration::from_millis(10)));
+        thread::sleep(Duration::from_millis(50));
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,158 @@
+const DIAGONAL_OFFSETS: [(isize, isize); 4] = [(-1, 1), (1, 1), (1, -1), (-1, -1)];
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+// Discriminants are stable so a direction can index per-direction arrays.
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub enum Direction {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+impl Direction {
+    // Clockwise from Up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self == Direction::Left || self == Direction::Right
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    // (row, col) delta for one step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+impl Coord {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    // `bounds` is (num_rows, num_cols). Returns None if the result would fall
+    // off either edge.
+    pub fn offset(self, (d_row, d_col): (isize, isize), bounds: (usize, usize)) -> Option<Self> {
+        let row = self.row.checked_add_signed(d_row)?;
+        let col = self.col.checked_add_signed(d_col)?;
+        (row < bounds.0 && col < bounds.1).then_some(Self::new(row, col))
+    }
+
+    pub fn step(self, dir: Direction, bounds: (usize, usize)) -> Option<Self> {
+        self.offset(dir.offset(), bounds)
+    }
+
+    // Orthogonal neighbours, clockwise from Up, skipping any out of bounds.
+    pub fn neighbors4(self, bounds: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(dir, bounds))
+    }
+
+    // Orthogonal and diagonal neighbours, clockwise from Up, skipping any out
+    // of bounds.
+    pub fn neighbors8(self, bounds: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .zip(DIAGONAL_OFFSETS)
+            .flat_map(|(dir, diagonal)| [dir.offset(), diagonal])
+            .filter_map(move |offset| self.offset(offset, bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_ne!(dir.is_horizontal(), dir.turn_left().is_horizontal());
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Down.turn_left(), Direction::Right);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert!(Direction::Left.is_horizontal());
+        assert!(Direction::Up.is_vertical());
+    }
+
+    #[test]
+    fn test_step() {
+        let bounds = (3, 4);
+        assert_eq!(Coord::new(0, 0).step(Direction::Up, bounds), None);
+        assert_eq!(Coord::new(0, 0).step(Direction::Left, bounds), None);
+        assert_eq!(Coord::new(2, 3).step(Direction::Down, bounds), None);
+        assert_eq!(Coord::new(2, 3).step(Direction::Right, bounds), None);
+        assert_eq!(
+            Coord::new(1, 1).step(Direction::Right, bounds),
+            Some(Coord::new(1, 2))
+        );
+        assert_eq!(
+            Coord::new(1, 1).step(Direction::Up, bounds),
+            Some(Coord::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let bounds = (2, 3);
+        assert_eq!(
+            Coord::new(0, 0).neighbors4(bounds).collect::<Vec<Coord>>(),
+            vec![Coord::new(0, 1), Coord::new(1, 0)]
+        );
+        assert_eq!(
+            Coord::new(0, 1).neighbors8(bounds).collect::<Vec<Coord>>(),
+            vec![
+                Coord::new(0, 2),
+                Coord::new(1, 2),
+                Coord::new(1, 1),
+                Coord::new(1, 0),
+                Coord::new(0, 0),
+            ]
+        );
+        assert_eq!(Coord::new(1, 1).neighbors8(bounds).count(), 5);
+    }
+}
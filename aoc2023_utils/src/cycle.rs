@@ -0,0 +1,210 @@
+use crate::{cancel, log_debug};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where an iterated state machine starts repeating itself: the state after
+// `start` steps is the first one that comes around again, every `len` steps.
+// Only meaningful for machines with finitely many states, since there's no
+// cycle to find otherwise and the searches below never return.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Cycle {
+    pub start: u64,
+    pub len: u64,
+}
+
+impl Cycle {
+    // The smallest number of steps that ends on the same state as `n` steps.
+    pub fn reduce(&self, n: impl Into<u128>) -> u64 {
+        let n = n.into();
+        let start = self.start as u128;
+        if n < start {
+            n as u64
+        } else {
+            (start + (n - start) % self.len as u128) as u64
+        }
+    }
+}
+
+// Steps from `initial`, keeping every state seen, until a state repeats or `n`
+// steps have been taken, whichever is first. The cycle is None if it's `n`.
+fn run_until_repeat<T: Hash + Eq + Clone>(
+    initial: &T,
+    mut step: impl FnMut(&T) -> T,
+    n: Option<u128>,
+) -> (Vec<T>, Option<Cycle>) {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial.clone()];
+    loop {
+        cancel::check();
+        let num_steps = states.len() - 1;
+        if n == Some(num_steps as u128) {
+            return (states, None);
+        }
+
+        let next = step(&states[num_steps]);
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start: start as u64,
+                len: (num_steps + 1 - start) as u64,
+            };
+            log_debug!("found {cycle:?} after {} steps", num_steps + 1);
+            return (states, Some(cycle));
+        }
+        seen.insert(next.clone(), num_steps + 1);
+        states.push(next);
+    }
+}
+
+// Finds the cycle by remembering every state, so it takes as many steps as
+// there are distinct states and no more.
+pub fn find<T: Hash + Eq + Clone>(initial: &T, step: impl FnMut(&T) -> T) -> Cycle {
+    run_until_repeat(initial, step, None).1.unwrap()
+}
+
+// The state after `n` steps, skipping over as many trips around the cycle as
+// it can. It stops early if `n` comes before the first repeat.
+pub fn nth<T: Hash + Eq + Clone>(initial: &T, step: impl FnMut(&T) -> T, n: impl Into<u128>) -> T {
+    let n = n.into();
+    let (mut states, cycle) = run_until_repeat(initial, step, Some(n));
+    match cycle {
+        Some(cycle) => states.swap_remove(cycle.reduce(n) as usize),
+        None => states.pop().unwrap(),
+    }
+}
+
+// Brent's algorithm: only ever holds two states, for when they're too big or
+// too many to keep around, at the cost of stepping through the start of the
+// sequence a few more times than `find()` does.
+pub fn find_brent<T: Eq + Clone>(initial: &T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // The hare runs ahead and the tortoise jumps to it at every power of two
+    // until the hare laps it, which gives the length.
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        cancel::check();
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // With the hare a whole cycle ahead, the two first meet at its start.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        cancel::check();
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let cycle = Cycle { start, len };
+    log_debug!("found {cycle:?}");
+    cycle
+}
+
+// `nth()` for states that can't all be kept around.
+pub fn nth_brent<T: Eq + Clone>(
+    initial: &T,
+    mut step: impl FnMut(&T) -> T,
+    n: impl Into<u128>,
+) -> T {
+    let num_steps = find_brent(initial, &mut step).reduce(n);
+    let mut state = initial.clone();
+    for _ in 0..num_steps {
+        cancel::check();
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0..=6 run straight into 7..=29, which then loops back around to 7.
+    fn step(n: &u32) -> u32 {
+        if *n == 29 {
+            7
+        } else {
+            n + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle { start: 7, len: 23 };
+
+    fn brute_force(n: u64) -> u32 {
+        (0..n).fold(0, |state, _| step(&state))
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(&0, step), CYCLE);
+        assert_eq!(find(&10, step), Cycle { start: 0, len: 23 });
+        assert_eq!(find(&0, |n| (n + 1) % 5), Cycle { start: 0, len: 5 });
+        assert_eq!(find(&3, |_| 4), Cycle { start: 1, len: 1 });
+        assert_eq!(find(&4, |_| 4), Cycle { start: 0, len: 1 });
+    }
+
+    #[test]
+    fn test_find_brent() {
+        assert_eq!(find_brent(&0, step), CYCLE);
+        assert_eq!(find_brent(&10, step), Cycle { start: 0, len: 23 });
+        assert_eq!(find_brent(&0, |n| (n + 1) % 5), Cycle { start: 0, len: 5 });
+        assert_eq!(find_brent(&3, |_| 4), Cycle { start: 1, len: 1 });
+        assert_eq!(find_brent(&4, |_| 4), Cycle { start: 0, len: 1 });
+    }
+
+    #[test]
+    fn test_find_agrees_with_brent() {
+        // x -> x^2 + 1 mod m, the usual rho-shaped sequence.
+        for m in 2..200_u64 {
+            let step = |x: &u64| (x * x + 1) % m;
+            assert_eq!(find(&0, step), find_brent(&0, step), "mod {m}");
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(CYCLE.reduce(0_u64), 0);
+        assert_eq!(CYCLE.reduce(6_u64), 6);
+        assert_eq!(CYCLE.reduce(7_u64), 7);
+        assert_eq!(CYCLE.reduce(29_u64), 29);
+        assert_eq!(CYCLE.reduce(30_u64), 7);
+        assert_eq!(CYCLE.reduce(7 + 23 * 1000 + 5_u64), 12);
+        assert_eq!(CYCLE.reduce(u128::MAX), 7 + ((u128::MAX - 7) % 23) as u64);
+    }
+
+    #[test]
+    fn test_nth() {
+        for n in 0..100 {
+            assert_eq!(nth(&0, step, n), brute_force(n), "n = {n}");
+            assert_eq!(nth_brent(&0, step, n), brute_force(n), "n = {n}");
+        }
+
+        let big = 1_000_000_000_000_u64;
+        let expected = brute_force(CYCLE.reduce(big));
+        assert_eq!(nth(&0, step, big), expected);
+        assert_eq!(nth_brent(&0, step, big), expected);
+        assert_eq!(
+            nth(&0, step, big as u128 * 1_000_000_000),
+            nth(&0, step, CYCLE.reduce(big as u128 * 1_000_000_000))
+        );
+    }
+
+    #[test]
+    fn test_nth_stops_at_n() {
+        // Counting up forever never repeats, so this only returns because
+        // it doesn't look any further than it has to.
+        assert_eq!(nth(&0_u64, |n| n + 1, 1000_u64), 1000);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::ops::Range;
+
+// Bounds the shrinking loop in case a shrinker keeps producing candidates that
+// still fail without actually getting any smaller.
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+// Differential testing: runs a reference implementation (usually the slow,
+// obviously correct one) and a candidate on the same generated inputs.
+pub struct Differ<T, A> {
+    pub generate: fn(u64) -> T,
+    pub shrink: fn(&T) -> Vec<T>,
+    pub reference: fn(&T) -> A,
+    pub candidate: fn(&T) -> A,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Mismatch<T, A> {
+    pub seed: u64,
+    pub original: T,
+    pub minimized: T,
+    pub num_shrinks: usize,
+    pub reference: A,
+    pub candidate: A,
+}
+
+impl<T: Clone, A: PartialEq> Differ<T, A> {
+    fn mismatch_on(&self, input: &T) -> Option<(A, A)> {
+        let reference = (self.reference)(input);
+        let candidate = (self.candidate)(input);
+        (reference != candidate).then_some((reference, candidate))
+    }
+
+    // Returns the first seed whose input the two disagree on, with the input
+    // shrunk for as long as some smaller version still disagrees.
+    pub fn find_mismatch(&self, seeds: Range<u64>) -> Option<Mismatch<T, A>> {
+        let (seed, original, mut results) = seeds.into_iter().find_map(|seed| {
+            let input = (self.generate)(seed);
+            self.mismatch_on(&input)
+                .map(|results| (seed, input, results))
+        })?;
+
+        let mut minimized = original.clone();
+        let mut num_shrinks = 0;
+        while num_shrinks < MAX_SHRINK_STEPS {
+            let smaller = (self.shrink)(&minimized)
+                .into_iter()
+                .find_map(|input| self.mismatch_on(&input).map(|results| (input, results)));
+            match smaller {
+                Some((input, smaller_results)) => {
+                    minimized = input;
+                    results = smaller_results;
+                    num_shrinks += 1;
+                }
+                None => break,
+            }
+        }
+
+        let (reference, candidate) = results;
+        Some(Mismatch {
+            seed,
+            original,
+            minimized,
+            num_shrinks,
+            reference,
+            candidate,
+        })
+    }
+
+    pub fn assert_agree(&self, seeds: Range<u64>)
+    where
+        T: fmt::Display,
+        A: fmt::Debug,
+    {
+        if let Some(mismatch) = self.find_mismatch(seeds) {
+            panic!("{mismatch}");
+        }
+    }
+}
+
+impl<T: fmt::Display, A: fmt::Debug> fmt::Display for Mismatch<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "seed {} disagrees (shrunk {} times), reference {:?} vs candidate {:?} on:",
+            self.seed, self.num_shrinks, self.reference, self.candidate
+        )?;
+        write!(f, "{}", self.minimized)
+    }
+}
+
+// Shrinking helpers
+
+// Every copy of `items` with one item left out.
+pub fn without_each<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    (0..items.len())
+        .map(|idx| {
+            let mut fewer = items.to_vec();
+            fewer.remove(idx);
+            fewer
+        })
+        .collect()
+}
+
+// Every copy of `s` with one char replaced by each of `replacements`, skipping
+// the ones that wouldn't change anything.
+pub fn with_each_char_replaced(s: &str, replacements: &[char]) -> Vec<String> {
+    let chars = s.chars().collect::<Vec<char>>();
+    let mut results = vec![];
+    for (idx, c) in chars.iter().enumerate() {
+        for replacement in replacements.iter().filter(|r| *r != c) {
+            let mut replaced = chars.clone();
+            replaced[idx] = *replacement;
+            results.push(replaced.into_iter().collect());
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sums a list, except the candidate forgets about 7s.
+    fn buggy_differ() -> Differ<Vec<u32>, u32> {
+        Differ {
+            generate: |seed| (0..seed as u32).map(|n| n * 3 % 10).collect(),
+            shrink: |nums| without_each(nums),
+            reference: |nums| nums.iter().sum(),
+            candidate: |nums| nums.iter().filter(|n| **n != 7).sum(),
+        }
+    }
+
+    #[test]
+    fn test_find_mismatch() {
+        let differ = buggy_differ();
+        assert_eq!(differ.find_mismatch(0..10), None);
+
+        let mismatch = differ.find_mismatch(0..20).unwrap();
+        assert_eq!(mismatch.seed, 10);
+        assert_eq!(mismatch.original, vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+        assert_eq!(mismatch.minimized, vec![7]);
+        assert_eq!(mismatch.num_shrinks, 9);
+        assert_eq!((mismatch.reference, mismatch.candidate), (7, 0));
+    }
+
+    #[test]
+    fn test_find_mismatch_agree() {
+        let differ = Differ {
+            reference: |nums: &Vec<u32>| nums.iter().sum(),
+            candidate: |nums: &Vec<u32>| nums.iter().rev().sum(),
+            ..buggy_differ()
+        };
+        assert_eq!(differ.find_mismatch(0..100), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "reference 7 vs candidate 0")]
+    fn test_assert_agree() {
+        Differ {
+            generate: |seed| seed.to_string(),
+            shrink: |s: &String| vec![s[1..].to_string()],
+            reference: |s: &String| s.chars().filter(|c| *c == '7').count() * 7,
+            candidate: |_| 0,
+        }
+        .assert_agree(0..10);
+    }
+
+    #[test]
+    fn test_without_each() {
+        assert_eq!(
+            without_each(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert!(without_each::<u32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_with_each_char_replaced() {
+        assert_eq!(
+            with_each_char_replaced("#?", &['.', '#']),
+            vec![".?", "#.", "##"]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{DynSolution, Part};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const EXAMPLES_DIR: &str = "examples";
+pub const GENERATED_FILE: &str = "fixture_tests.rs";
+
+const INPUT_EXT: &str = "txt";
+const EXPECTED_EXT: &str = "expected";
+
+// An example input, `NAME.txt`, and the answers listed for it in
+// `NAME.expected`. A part without an expected answer gets no test.
+#[derive(Debug, PartialEq)]
+struct Fixture {
+    name: String,
+    input_path: PathBuf,
+    expected: Vec<(Part, String)>,
+}
+
+fn parse_expected(s: &str) -> Result<Vec<(Part, String)>, String> {
+    let mut expected = vec![];
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, answer) = line
+            .split_once('=')
+            .ok_or(format!("expected `part = answer`, got: {line}"))?;
+        let part = match key.trim() {
+            "a" => Part::A,
+            "b" => Part::B,
+            key => return Err(format!("unknown part: {key}")),
+        };
+        let answer = answer.trim().trim_matches('"');
+        expected.push((part, answer.to_string()));
+    }
+
+    Ok(expected)
+}
+
+fn find_fixtures(dir: &Path) -> Result<Vec<Fixture>, String> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let entries = fs::read_dir(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+    let mut input_paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == INPUT_EXT))
+        .collect::<Vec<PathBuf>>();
+    input_paths.sort();
+
+    input_paths
+        .into_iter()
+        .map(|input_path| {
+            let expected_path = input_path.with_extension(EXPECTED_EXT);
+            let expected = fs::read_to_string(&expected_path)
+                .map_err(|err| format!("{}: {err}", expected_path.display()))?;
+            let expected = parse_expected(&expected)
+                .map_err(|err| format!("{}: {err}", expected_path.display()))?;
+            let name = input_path
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            Ok(Fixture {
+                name,
+                input_path,
+                expected,
+            })
+        })
+        .collect()
+}
+
+fn test_fn_name(fixture_name: &str, part: Part) -> String {
+    let name = fixture_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("example_{name}_{}", part.label())
+}
+
+fn gen_tests_code(fixtures: &[Fixture]) -> String {
+    let mut code = String::new();
+    for fixture in fixtures {
+        for (part, answer) in &fixture.expected {
+            writeln!(
+                code,
+                concat!(
+                    "#[test]\n",
+                    "fn {}() {{\n",
+                    "    aoc2023_utils::fixtures::check_fixture(\n",
+                    "        solution(),\n",
+                    "        {:?},\n",
+                    "        include_str!({:?}),\n",
+                    "        aoc2023_utils::Part::{:?},\n",
+                    "        {:?},\n",
+                    "    );\n",
+                    "}}\n",
+                ),
+                test_fn_name(&fixture.name, *part),
+                fixture.name,
+                fixture.input_path.to_string_lossy(),
+                part,
+                answer,
+            )
+            .unwrap();
+        }
+    }
+    code
+}
+
+// Call from a day's build script. Writes one test per fixture and part to
+// $OUT_DIR, to be pulled in with `fixture_tests!`.
+pub fn generate_tests() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("not run from a build script");
+    let out_dir = env::var("OUT_DIR").expect("not run from a build script");
+
+    let dir = Path::new(&manifest_dir).join(EXAMPLES_DIR);
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let fixtures = find_fixtures(&dir).unwrap_or_else(|err| panic!("bad example fixture: {err}"));
+    fs::write(
+        Path::new(&out_dir).join(GENERATED_FILE),
+        gen_tests_code(&fixtures),
+    )
+    .expect("couldn't write generated fixture tests");
+}
+
+pub fn check_fixture(
+    solution: &dyn DynSolution,
+    name: &str,
+    input: &str,
+    part: Part,
+    expected: &str,
+) {
+    assert!(
+        solution.has_part(part),
+        "example {name} has an answer for part {} but the solution doesn't",
+        part.label()
+    );
+
+    let input = crate::normalize_input(input.as_bytes().to_vec(), name)
+        .unwrap_or_else(|err| panic!("{err}"));
+    let parsed = solution.parse_any(&input);
+    let answer = solution.solve_any(parsed.as_ref(), part);
+    assert_eq!(
+        answer,
+        expected,
+        "wrong answer for example {name}, part {}",
+        part.label()
+    );
+}
+
+// Expands to a test module running every fixture in the crate's `examples`
+// directory against `$solution`. Needs a build script calling
+// `fixtures::generate_tests`.
+#[macro_export]
+macro_rules! fixture_tests {
+    ($solution:expr) => {
+        #[cfg(test)]
+        mod fixture_tests {
+            use super::*;
+
+            #[allow(dead_code)]
+            fn solution() -> &'static dyn $crate::DynSolution {
+                &$solution
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        type Parsed = usize;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        const HAS_PART2: bool = false;
+
+        fn parse(&self, input: &str) -> Self::Parsed {
+            input.lines().count()
+        }
+
+        fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+            *parsed
+        }
+    }
+
+    struct FirstLine;
+
+    impl Solution for FirstLine {
+        type Parsed = String;
+        type Answer1 = String;
+        type Answer2 = String;
+
+        const HAS_PART2: bool = false;
+
+        fn parse(&self, input: &str) -> Self::Parsed {
+            input.lines().next().unwrap_or_default().to_string()
+        }
+
+        fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+            parsed.clone()
+        }
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            parse_expected("# from the puzzle text\na = 142\n\nb = \"281\"\n"),
+            Ok(vec![
+                (Part::A, "142".to_string()),
+                (Part::B, "281".to_string())
+            ])
+        );
+        assert_eq!(parse_expected(""), Ok(vec![]));
+        assert!(parse_expected("c = 1\n").is_err());
+        assert!(parse_expected("a 1\n").is_err());
+    }
+
+    #[test]
+    fn test_find_fixtures() {
+        let dir = env::temp_dir().join(format!("aoc_fixtures_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("sample2.txt"), "x\n").unwrap();
+        fs::write(dir.join("sample2.expected"), "b = 7\n").unwrap();
+        fs::write(dir.join("sample1.txt"), "x\ny\n").unwrap();
+        fs::write(dir.join("sample1.expected"), "a = 2\n").unwrap();
+        fs::write(dir.join("notes.md"), "ignored\n").unwrap();
+
+        let fixtures = find_fixtures(&dir);
+
+        fs::write(dir.join("sample3.txt"), "x\n").unwrap();
+        let missing_expected = find_fixtures(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            fixtures,
+            Ok(vec![
+                Fixture {
+                    name: "sample1".to_string(),
+                    input_path: dir.join("sample1.txt"),
+                    expected: vec![(Part::A, "2".to_string())],
+                },
+                Fixture {
+                    name: "sample2".to_string(),
+                    input_path: dir.join("sample2.txt"),
+                    expected: vec![(Part::B, "7".to_string())],
+                },
+            ])
+        );
+        assert!(missing_expected.is_err());
+        assert_eq!(find_fixtures(&dir.join("nope")), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_gen_tests_code() {
+        let fixtures = vec![Fixture {
+            name: "edge-case".to_string(),
+            input_path: PathBuf::from("/x/edge-case.txt"),
+            expected: vec![(Part::B, "5".to_string())],
+        }];
+        assert_eq!(
+            gen_tests_code(&fixtures),
+            concat!(
+                "#[test]\n",
+                "fn example_edge_case_b() {\n",
+                "    aoc2023_utils::fixtures::check_fixture(\n",
+                "        solution(),\n",
+                "        \"edge-case\",\n",
+                "        include_str!(\"/x/edge-case.txt\"),\n",
+                "        aoc2023_utils::Part::B,\n",
+                "        \"5\",\n",
+                "    );\n",
+                "}\n",
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_check_fixture() {
+        check_fixture(&LineCount, "crlf", "a\r\nb\r\n", Part::A, "2");
+        check_fixture(&FirstLine, "bom", "\u{feff}abc\r\n", Part::A, "abc");
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong answer for example three, part a")]
+    fn test_check_fixture_wrong_answer() {
+        check_fixture(&LineCount, "three", "a\nb\n", Part::A, "3");
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't")]
+    fn test_check_fixture_missing_part() {
+        check_fixture(&LineCount, "two", "a\nb\n", Part::B, "2");
+    }
+}
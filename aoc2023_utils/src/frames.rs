@@ -0,0 +1,443 @@
+use crate::image::{Image, Rgb};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Hundredths of a second between GIF frames.
+const GIF_FRAME_DELAY: u16 = 5;
+const GIF_MAX_CODE: u16 = 4095;
+
+// One step of a simulation, as text for text frames and as an image for the
+// rest. Only the one the recorder wants gets built.
+pub trait Frame {
+    fn to_text(&self) -> String;
+    fn to_image(&self) -> Image;
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum FrameFormat {
+    Text,
+    Ppm,
+    Png,
+}
+
+impl FrameFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FrameFormat::Text => "txt",
+            FrameFormat::Ppm => "ppm",
+            FrameFormat::Png => "png",
+        }
+    }
+}
+
+enum Sink {
+    Files(PathBuf, FrameFormat),
+    Gif(PathBuf, Option<GifWriter<BufWriter<File>>>),
+}
+
+// Writes each frame it's handed either to its own numbered file or into an
+// animated GIF, neither of which is created until the first frame comes
+// along. The step loops it's hooked into have no way to stop on an error, so
+// the first one is held on to, nothing more is written, and `finish()` hands
+// it back.
+pub struct Recorder {
+    sink: Sink,
+    scale: usize,
+    num_frames: usize,
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    pub fn to_files(dir: &Path, format: FrameFormat, scale: usize) -> Self {
+        Self::new(Sink::Files(dir.to_path_buf(), format), scale)
+    }
+
+    pub fn to_gif(path: &Path, scale: usize) -> Self {
+        Self::new(Sink::Gif(path.to_path_buf(), None), scale)
+    }
+
+    fn new(sink: Sink, scale: usize) -> Self {
+        Self {
+            sink,
+            scale,
+            num_frames: 0,
+            error: None,
+        }
+    }
+
+    pub fn record(&mut self, frame: &impl Frame) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(err) = self.write_frame(frame) {
+            self.error = Some(err);
+        }
+    }
+
+    fn write_frame(&mut self, frame: &impl Frame) -> io::Result<()> {
+        self.num_frames += 1;
+        match &mut self.sink {
+            Sink::Files(dir, format) => {
+                if self.num_frames == 1 {
+                    fs::create_dir_all(&dir)?;
+                }
+                let path = dir.join(format!(
+                    "frame-{:05}.{}",
+                    self.num_frames,
+                    format.extension()
+                ));
+                match format {
+                    FrameFormat::Text => fs::write(path, frame.to_text()),
+                    _ => frame.to_image().scaled(self.scale).save(&path),
+                }
+            }
+            Sink::Gif(path, writer) => {
+                if writer.is_none() {
+                    *writer = Some(GifWriter::new(BufWriter::new(File::create(path)?)));
+                }
+                let writer = writer.as_mut().unwrap();
+                writer.write_frame(&frame.to_image().scaled(self.scale))
+            }
+        }
+    }
+
+    // The number of frames written.
+    pub fn finish(self) -> io::Result<usize> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if let Sink::Gif(_, Some(writer)) = self.sink {
+            writer.finish()?;
+        }
+        Ok(self.num_frames)
+    }
+}
+
+// Every frame gets its own colour table, so nothing needs to know all the
+// colours up front. The screen size comes from the first frame.
+struct GifWriter<W: Write> {
+    writer: W,
+    size: Option<(usize, usize)>,
+}
+
+impl<W: Write> GifWriter<W> {
+    fn new(writer: W) -> Self {
+        Self { writer, size: None }
+    }
+
+    fn write_header(&mut self, width: usize, height: usize) -> io::Result<()> {
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::other(format!(
+                "{width}x{height} is too big for a GIF"
+            )));
+        }
+
+        self.writer.write_all(b"GIF89a")?;
+        self.writer.write_all(&(width as u16).to_le_bytes())?;
+        self.writer.write_all(&(height as u16).to_le_bytes())?;
+        // No global colour table, background colour 0, square pixels.
+        self.writer.write_all(&[0, 0, 0])?;
+        // Loop forever.
+        self.writer
+            .write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")
+    }
+
+    fn write_frame(&mut self, image: &Image) -> io::Result<()> {
+        let size = (image.width(), image.height());
+        match self.size {
+            None => {
+                self.write_header(size.0, size.1)?;
+                self.size = Some(size);
+            }
+            Some(first_size) if first_size != size => {
+                return Err(io::Error::other("GIF frames change size"));
+            }
+            Some(_) => {}
+        }
+
+        let (palette, indices) = palettize(image);
+
+        self.writer.write_all(b"\x21\xf9\x04\x00")?;
+        self.writer.write_all(&GIF_FRAME_DELAY.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+
+        self.writer.write_all(b"\x2c\x00\x00\x00\x00")?;
+        self.writer.write_all(&(size.0 as u16).to_le_bytes())?;
+        self.writer.write_all(&(size.1 as u16).to_le_bytes())?;
+        // A local colour table with 2^8 entries.
+        self.writer.write_all(&[0x87])?;
+        let mut table = palette.concat();
+        table.resize(256 * 3, 0);
+        self.writer.write_all(&table)?;
+
+        self.writer.write_all(&[8])?;
+        for block in lzw_encode(&indices).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0])
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        self.writer.write_all(&[0x3b])?;
+        self.writer.flush()
+    }
+}
+
+// The image's colours and each pixel's index into them. Anything with more
+// than 256 colours is cut down to 3 bits of red, 3 of green and 2 of blue.
+fn palettize(image: &Image) -> (Vec<Rgb>, Vec<u8>) {
+    let pixels = (0..image.height())
+        .flat_map(|y| (0..image.width()).map(move |x| (x, y)))
+        .map(|(x, y)| image.get(x, y))
+        .collect::<Vec<Rgb>>();
+
+    let mut palette = vec![];
+    let mut palette_idxs = HashMap::new();
+    for pixel in &pixels {
+        palette_idxs.entry(*pixel).or_insert_with(|| {
+            palette.push(*pixel);
+            palette.len() - 1
+        });
+    }
+
+    if palette.len() <= 256 {
+        let indices = pixels
+            .iter()
+            .map(|pixel| palette_idxs[pixel] as u8)
+            .collect();
+        return (palette, indices);
+    }
+
+    let palette = (0..=255_u8)
+        .map(|idx| [idx & 0xe0, (idx << 3) & 0xe0, (idx << 6) & 0xc0])
+        .collect();
+    let indices = pixels
+        .iter()
+        .map(|[r, g, b]| (r & 0xe0) | ((g & 0xe0) >> 3) | (b >> 6))
+        .collect();
+    (palette, indices)
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: u32,
+    num_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.bits |= (code as u32) << self.num_bits;
+        self.num_bits += width;
+        while self.num_bits >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.num_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_bits > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+        self.bytes
+    }
+}
+
+// GIF's flavour of LZW for 8-bit indices: codes start out 9 bits wide and
+// grow up to 12, and the table starts over with a clear code once it's full.
+fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    const CLEAR: u16 = 256;
+    const END: u16 = 257;
+
+    let mut out = BitWriter {
+        bytes: vec![],
+        bits: 0,
+        num_bits: 0,
+    };
+    let mut table = HashMap::<(u16, u8), u16>::new();
+    let mut next_code = END + 1;
+    let mut width = 9;
+
+    out.write(CLEAR, width);
+    let mut indices = indices.iter();
+    let Some(first) = indices.next() else {
+        out.write(END, width);
+        return out.finish();
+    };
+
+    let mut prefix = *first as u16;
+    for idx in indices {
+        if let Some(code) = table.get(&(prefix, *idx)) {
+            prefix = *code;
+            continue;
+        }
+
+        out.write(prefix, width);
+        if next_code <= GIF_MAX_CODE {
+            table.insert((prefix, *idx), next_code);
+            next_code += 1;
+            // Decoders add to their table a code behind this one, so they
+            // only need the wider codes from here on.
+            if next_code > 1 << width && width < 12 {
+                width += 1;
+            }
+        } else {
+            out.write(CLEAR, width);
+            table.clear();
+            next_code = END + 1;
+            width = 9;
+        }
+        prefix = *idx as u16;
+    }
+
+    out.write(prefix, width);
+    out.write(END, width);
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::{BLACK, WHITE};
+    use crate::Rng;
+
+    struct Dots(usize);
+
+    impl Frame for Dots {
+        fn to_text(&self) -> String {
+            ".".repeat(self.0)
+        }
+
+        fn to_image(&self) -> Image {
+            let mut image = Image::new(3, 1, BLACK);
+            image.set(self.0 % 3, 0, WHITE);
+            image
+        }
+    }
+
+    // Decodes the way the GIF spec says to, as a check on lzw_encode().
+    fn lzw_decode(bytes: &[u8]) -> Vec<u8> {
+        let mut bits = bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1));
+        let mut read = |width: u32| {
+            (0..width).fold(0_u16, |code, bit| {
+                code | ((bits.next().unwrap() as u16) << bit)
+            })
+        };
+
+        let mut table = vec![];
+        let mut width = 9;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut out = vec![];
+        loop {
+            let code = read(width);
+            if code == 256 {
+                table = (0..=255).map(|idx| vec![idx]).collect();
+                table.extend([vec![], vec![]]);
+                width = 9;
+                prev = None;
+                continue;
+            }
+            if code == 257 {
+                return out;
+            }
+
+            let entry = match &prev {
+                None => table[code as usize].clone(),
+                Some(prev) => {
+                    let entry = match table.get(code as usize) {
+                        Some(entry) => entry.clone(),
+                        None => [&prev[..], &prev[..1]].concat(),
+                    };
+                    if table.len() <= GIF_MAX_CODE as usize {
+                        table.push([&prev[..], &entry[..1]].concat());
+                        if table.len() == 1 << width && width < 12 {
+                            width += 1;
+                        }
+                    }
+                    entry
+                }
+            };
+            out.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_encode() {
+        let mut rng = Rng::new(1);
+        let inputs = [
+            vec![],
+            vec![7],
+            vec![0; 10_000],
+            (0..10_000).map(|n| (n % 7) as u8).collect(),
+            (0..50_000).map(|_| rng.range(0..4) as u8).collect(),
+            (0..50_000).map(|_| rng.range(0..256) as u8).collect(),
+        ];
+        for input in inputs {
+            assert_eq!(lzw_decode(&lzw_encode(&input)), input);
+        }
+    }
+
+    #[test]
+    fn test_palettize() {
+        let mut image = Image::new(2, 2, BLACK);
+        image.set(1, 1, WHITE);
+        assert_eq!(palettize(&image), (vec![BLACK, WHITE], vec![0, 0, 0, 1]));
+
+        let mut image = Image::new(300, 1, BLACK);
+        (0..300).for_each(|x| image.set(x, 0, [x as u8, (x / 256) as u8, 0xff]));
+        let (palette, indices) = palettize(&image);
+        assert_eq!(palette.len(), 256);
+        assert_eq!(palette[indices[0xe3] as usize], [0xe0, 0, 0xc0]);
+    }
+
+    #[test]
+    fn test_recorder() {
+        let dir = std::env::temp_dir().join(format!("aoc_frames_{}", std::process::id()));
+
+        let mut recorder = Recorder::to_files(&dir.join("txt"), FrameFormat::Text, 1);
+        (1..=3).for_each(|n| recorder.record(&Dots(n)));
+        assert_eq!(recorder.finish().unwrap(), 3);
+        assert_eq!(
+            fs::read_to_string(dir.join("txt/frame-00002.txt")).unwrap(),
+            ".."
+        );
+
+        let path = dir.join("dots.gif");
+        let mut recorder = Recorder::to_gif(&path, 2);
+        (1..=3).for_each(|n| recorder.record(&Dots(n)));
+        assert_eq!(recorder.finish().unwrap(), 3);
+        let gif = fs::read(&path).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x06\x00\x02\x00"));
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert_eq!(gif.windows(2).filter(|w| w == b"\x21\xf9").count(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recorder_error() {
+        struct Growing(usize);
+
+        impl Frame for Growing {
+            fn to_text(&self) -> String {
+                String::new()
+            }
+
+            fn to_image(&self) -> Image {
+                Image::new(self.0, 1, BLACK)
+            }
+        }
+
+        let path = std::env::temp_dir().join(format!("aoc_frames_{}.gif", std::process::id()));
+        let mut recorder = Recorder::to_gif(&path, 1);
+        (1..=3).for_each(|n| recorder.record(&Growing(n)));
+        assert!(recorder.finish().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
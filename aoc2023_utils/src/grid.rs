@@ -0,0 +1,279 @@
+use crate::Coord;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// Row-major grid of cells addressed by (row, col).
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    num_rows: usize,
+    num_cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(num_rows: usize, num_cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            num_rows,
+            num_cols,
+            cells: vec![fill; num_rows * num_cols],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let num_rows = rows.len();
+        let num_cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == num_cols),
+            "all grid rows must be the same length"
+        );
+
+        Self {
+            num_rows,
+            num_cols,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn parse(s: &str, mut cell_mapper: impl FnMut(char) -> T) -> Self {
+        Self::from_rows(
+            s.lines()
+                .map(|line| line.chars().map(&mut cell_mapper).collect())
+                .collect(),
+        )
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    // (num_rows, num_cols), as taken by `Coord::step`.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.num_rows, self.num_cols)
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.num_rows && col < self.num_cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if self.in_bounds(row, col) {
+            Some(&self.cells[row * self.num_cols + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if self.in_bounds(row, col) {
+            Some(&mut self.cells[row * self.num_cols + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.num_cols..(row + 1) * self.num_cols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.num_rows).map(|row| self.row(row))
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.num_cols, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.num_cols)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.num_cols).map(|col| self.col(col))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| ((idx / self.num_cols, idx % self.num_cols), cell))
+    }
+
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(pred)
+            .map(|idx| (idx / self.num_cols, idx % self.num_cols))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Orthogonal neighbours, clockwise from north, skipping any off the grid.
+    pub fn neighbors4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        Coord::new(row, col)
+            .neighbors4(self.bounds())
+            .map(|coord| (coord.row, coord.col))
+    }
+
+    // Orthogonal and diagonal neighbours, clockwise from north, skipping any
+    // off the grid.
+    pub fn neighbors8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        Coord::new(row, col)
+            .neighbors8(self.bounds())
+            .map(|coord| (coord.row, coord.col))
+    }
+
+    pub fn to_string_with(&self, mut cell_to_char: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut cell_to_char).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is off the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is off the grid"))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        &self[(coord.row, coord.col)]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        &mut self[(coord.row, coord.col)]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row_idx, row) in self.rows().enumerate() {
+            if row_idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = concat!(
+        "123\n", //
+        "456\n", //
+    );
+
+    fn sample_grid() -> Grid<u32> {
+        Grid::parse(SAMPLE_INPUT, |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample_grid();
+        assert_eq!(grid.num_rows(), 2);
+        assert_eq!(grid.num_cols(), 3);
+        assert_eq!(grid, Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_rows_ragged() {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = sample_grid();
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+
+        *grid.get_mut(0, 1).unwrap() = 9;
+        grid[(1, 0)] = 8;
+        assert_eq!(grid[(0, 1)], 9);
+        assert_eq!(grid[(1, 0)], 8);
+        assert_eq!(grid[Coord::new(1, 2)], 6);
+    }
+
+    #[test]
+    fn test_rows_and_cols() {
+        let grid = sample_grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<&[u32]>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.col(1).copied().collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(
+            grid.cols()
+                .map(|col| col.copied().collect())
+                .collect::<Vec<Vec<u32>>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn test_iter_and_position() {
+        let grid = sample_grid();
+        assert_eq!(grid.iter().map(|(coord, _)| coord).nth(4), Some((1, 1)));
+        assert_eq!(grid.position(|cell| *cell == 6), Some((1, 2)));
+        assert_eq!(grid.position(|cell| *cell == 7), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = sample_grid();
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<(usize, usize)>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors4(1, 1).collect::<Vec<(usize, usize)>>(),
+            vec![(0, 1), (1, 2), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8(0, 1).collect::<Vec<(usize, usize)>>(),
+            vec![(0, 2), (1, 2), (1, 1), (1, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = sample_grid();
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            grid.map(|cell| cell % 2 == 0)
+                .to_string_with(|even| if *even { '#' } else { '.' }),
+            ".#.\n#.#"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::Grid;
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+// Stored (uncompressed) deflate blocks can't hold any more than this.
+const MAX_STORED_BLOCK_LEN: usize = 0xffff;
+
+// An RGB image, written out without pulling in an image crate: PPM because
+// it's trivial and PNG because everything can open it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    // One pixel per cell; see `scaled()` for anything bigger.
+    pub fn from_grid<T>(grid: &Grid<T>, mut cell_to_color: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.num_cols(),
+            height: grid.num_rows(),
+            pixels: grid.rows().flatten().map(&mut cell_to_color).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    // Every pixel blown up to a `factor` x `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let mut scaled = Self::new(self.width * factor, self.height * factor, BLACK);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+        scaled
+    }
+
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels.concat())
+    }
+
+    pub fn write_png(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the only compression, filter and
+        // interlace methods there are.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Each scanline starts with its filter type, 0 for none.
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend(row.concat());
+        }
+
+        writer.write_all(PNG_SIGNATURE)?;
+        write_png_chunk(&mut writer, b"IHDR", &header)?;
+        write_png_chunk(&mut writer, b"IDAT", &zlib_stored(&scanlines))?;
+        write_png_chunk(&mut writer, b"IEND", &[])
+    }
+
+    // The format comes from the extension: .png or .ppm.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        if !matches!(extension, Some("png" | "ppm")) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("don't know how to write {}", path.display()),
+            ));
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        match extension {
+            Some("png") => self.write_png(&mut writer)?,
+            _ => self.write_ppm(&mut writer)?,
+        }
+        writer.flush()
+    }
+}
+
+fn write_png_chunk(writer: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    let crc = crc32(&[&kind[..], data].concat());
+    writer.write_all(&crc.to_be_bytes())
+}
+
+// A zlib stream of stored deflate blocks. The images are small enough that
+// not compressing them doesn't matter.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK_LEN).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        stream.push(blocks.peek().is_none() as u8);
+        stream.extend((block.len() as u16).to_le_bytes());
+        stream.extend((!(block.len() as u16)).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+
+    fn checkerboard() -> Image {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, true]]);
+        Image::from_grid(&grid, |on| if *on { RED } else { WHITE })
+    }
+
+    // Pulls the raw bytes back out of a stream from zlib_stored().
+    fn unstore(stream: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        let mut at = 2;
+        loop {
+            let is_final = stream[at] == 1;
+            let len = u16::from_le_bytes([stream[at + 1], stream[at + 2]]) as usize;
+            data.extend(&stream[at + 5..at + 5 + len]);
+            at += 5 + len;
+            if is_final {
+                break;
+            }
+        }
+        assert_eq!(stream[at..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let image = checkerboard();
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.get(0, 0), RED);
+        assert_eq!(image.get(1, 0), WHITE);
+        assert_eq!(image.get(0, 1), WHITE);
+    }
+
+    #[test]
+    fn test_scaled() {
+        let image = checkerboard().scaled(3);
+        assert_eq!((image.width(), image.height()), (6, 6));
+        assert_eq!(image.get(2, 2), RED);
+        assert_eq!(image.get(3, 2), WHITE);
+        assert_eq!(image.get(5, 5), RED);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut out = vec![];
+        Image::new(2, 1, RED).write_ppm(&mut out).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\xff\x00\x00\xff\x00\x00");
+    }
+
+    #[test]
+    fn test_write_png() {
+        let mut out = vec![];
+        checkerboard().write_png(&mut out).unwrap();
+        assert!(out.starts_with(PNG_SIGNATURE));
+        assert!(out.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+
+        let idat_at = out.windows(4).position(|w| w == b"IDAT").unwrap();
+        let len = u32::from_be_bytes(out[idat_at - 4..idat_at].try_into().unwrap()) as usize;
+        assert_eq!(
+            unstore(&out[idat_at + 4..idat_at + 4 + len]),
+            [&[0][..], &RED, &WHITE, &[0], &WHITE, &RED].concat()
+        );
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        for len in [0, 1, MAX_STORED_BLOCK_LEN, MAX_STORED_BLOCK_LEN * 2 + 5] {
+            let data = (0..len).map(|n| n as u8).collect::<Vec<u8>>();
+            assert_eq!(unstore(&zlib_stored(&data)), data);
+        }
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn test_save_unknown_extension() {
+        let err = Image::new(1, 1, RED)
+            .save(Path::new("out.gif"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
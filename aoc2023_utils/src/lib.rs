@@ -1,6 +1,213 @@
+pub mod cancel;
+pub mod coord;
+pub mod cycle;
+pub mod diff;
+pub mod fixtures;
+pub mod frames;
+pub mod grid;
+pub mod image;
+pub mod log;
+pub mod math;
+pub mod memo;
+pub mod par;
+pub mod rng;
+pub mod search;
+pub mod solution;
+
+pub use coord::{Coord, Direction};
+pub use grid::Grid;
+pub use image::{Image, Rgb};
+pub use rng::Rng;
+pub use solution::{DynSolution, Part, Solution};
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
+use std::io::{self, ErrorKind, Read};
+use std::path::PathBuf;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "inputs";
+const BOM: &str = "\u{feff}";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(String),
+    PermissionDenied(String),
+    NotUtf8(String),
+    Empty(String),
+    Io(String, io::Error),
+}
+
+impl InputError {
+    fn from_io(name: &str, err: io::Error) -> Self {
+        match err.kind() {
+            ErrorKind::NotFound => InputError::NotFound(name.to_string()),
+            ErrorKind::PermissionDenied => InputError::PermissionDenied(name.to_string()),
+            ErrorKind::InvalidData => InputError::NotUtf8(name.to_string()),
+            _ => InputError::Io(name.to_string(), err),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound(name) => write!(f, "input not found: {name}"),
+            InputError::PermissionDenied(name) => {
+                write!(f, "permission denied reading input: {name}")
+            }
+            InputError::NotUtf8(name) => write!(f, "input is not valid UTF-8: {name}"),
+            InputError::Empty(name) => write!(f, "input is empty: {name}"),
+            InputError::Io(name, err) => write!(f, "error reading input {name}: {err}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// Strips a leading BOM and converts CRLF line endings so the parsers only ever
+// see "\n", whatever editor last touched the file.
+fn normalize_input(bytes: Vec<u8>, name: &str) -> Result<String, InputError> {
+    let input = String::from_utf8(bytes).map_err(|_| InputError::NotUtf8(name.to_string()))?;
+    let input = input.strip_prefix(BOM).unwrap_or(&input);
+    if input.is_empty() {
+        return Err(InputError::Empty(name.to_string()));
+    }
+
+    Ok(input.replace("\r\n", "\n"))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    // Precedence: an explicit argument ("-" meaning stdin), then the file in
+    // $AOC_INPUT_DIR, then the file in the default "inputs" directory.
+    pub fn resolve(arg: Option<&str>, filename: &str) -> Self {
+        Self::do_resolve(arg, std::env::var_os(INPUT_DIR_VAR), filename)
+    }
+
+    fn do_resolve(arg: Option<&str>, input_dir: Option<OsString>, filename: &str) -> Self {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => {
+                let dir = input_dir
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR));
+                InputSource::File(dir.join(filename))
+            }
+        }
+    }
+
+    pub fn try_read(&self) -> Result<String, InputError> {
+        match self {
+            InputSource::Stdin => {
+                let name = self.to_string();
+                let mut bytes = vec![];
+                io::stdin()
+                    .read_to_end(&mut bytes)
+                    .map_err(|err| InputError::from_io(&name, err))?;
+                normalize_input(bytes, &name)
+            }
+            InputSource::File(path) => try_get_input(&path.to_string_lossy()),
+        }
+    }
+
+    pub fn read(&self) -> String {
+        self.try_read().unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "-"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub fn try_get_input(filename: &str) -> Result<String, InputError> {
+    let bytes = fs::read(filename).map_err(|err| InputError::from_io(filename, err))?;
+    normalize_input(bytes, filename)
+}
 
 pub fn get_input(filename: &str) -> String {
-    let err_msg = format!("Something went wrong reading the input file: {}", filename);
-    fs::read_to_string(filename).expect(&err_msg)
+    try_get_input(filename)
+        .unwrap_or_else(|err| panic!("Something went wrong reading the input file: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_input_source() {
+        assert_eq!(
+            InputSource::do_resolve(Some("-"), Some("/tmp/aoc".into()), "day05.txt"),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::do_resolve(Some("my/day05.txt"), Some("/tmp/aoc".into()), "day05.txt"),
+            InputSource::File(PathBuf::from("my/day05.txt"))
+        );
+        assert_eq!(
+            InputSource::do_resolve(None, Some("/tmp/aoc".into()), "day05.txt"),
+            InputSource::File(PathBuf::from("/tmp/aoc/day05.txt"))
+        );
+        assert_eq!(
+            InputSource::do_resolve(None, Some("".into()), "day05.txt"),
+            InputSource::File(PathBuf::from("inputs/day05.txt"))
+        );
+        assert_eq!(
+            InputSource::do_resolve(None, None, "day05.txt"),
+            InputSource::File(PathBuf::from("inputs/day05.txt"))
+        );
+    }
+
+    #[test]
+    fn test_normalize_input() {
+        assert_eq!(
+            normalize_input(b"ab\r\ncd\r\n\r\nef\n".to_vec(), "x").unwrap(),
+            "ab\ncd\n\nef\n"
+        );
+        assert_eq!(
+            normalize_input(b"\xef\xbb\xbfab\r\n".to_vec(), "x").unwrap(),
+            "ab\n"
+        );
+        assert!(matches!(
+            normalize_input(b"\xef\xbb\xbf".to_vec(), "x"),
+            Err(InputError::Empty(_))
+        ));
+        assert!(matches!(
+            normalize_input(vec![], "x"),
+            Err(InputError::Empty(_))
+        ));
+        assert!(matches!(
+            normalize_input(b"ab\xff".to_vec(), "x"),
+            Err(InputError::NotUtf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_get_input_not_found() {
+        assert!(matches!(
+            try_get_input("no/such/dir/day99.txt"),
+            Err(InputError::NotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Level {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+pub const DEFAULT_LEVEL: Level = Level::Warn;
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(DEFAULT_LEVEL as u8);
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    // 0 is the default level, each -v adds one and each -q takes one away.
+    pub fn from_verbosity(verbosity: i32) -> Self {
+        let idx = (DEFAULT_LEVEL as i32 + verbosity).clamp(0, Self::ALL.len() as i32 - 1);
+        Self::ALL[idx as usize]
+    }
+}
+
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn max_level() -> Level {
+    Level::ALL[MAX_LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+// Check this before building anything expensive that's only there to be
+// logged.
+pub fn enabled(level: Level) -> bool {
+    level <= max_level()
+}
+
+// Messages go to stderr so they never mix with answers on stdout.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level: $crate::log::Level = $level;
+        if $crate::log::enabled(level) {
+            eprintln!("[{}] {}", level.label(), format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_verbosity() {
+        assert_eq!(Level::from_verbosity(0), DEFAULT_LEVEL);
+        assert_eq!(Level::from_verbosity(1), Level::Info);
+        assert_eq!(Level::from_verbosity(2), Level::Debug);
+        assert_eq!(Level::from_verbosity(10), Level::Trace);
+        assert_eq!(Level::from_verbosity(-1), Level::Error);
+        assert_eq!(Level::from_verbosity(-10), Level::Error);
+    }
+
+    #[test]
+    fn test_default_level() {
+        // Other tests in this crate never change the level, so it's safe to
+        // read without racing them.
+        assert_eq!(max_level(), DEFAULT_LEVEL);
+        assert!(enabled(Level::Error));
+        assert!(enabled(Level::Warn));
+        assert!(!enabled(Level::Info));
+        assert!(!enabled(Level::Trace));
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Rem, Sub};
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn checked_abs<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        T::ZERO.checked_sub(n)
+    } else {
+        Some(n)
+    }
+}
+
+// Always non-negative. Only fails for signed types when the answer is one past
+// the type's max, e.g. gcd(i32::MIN, 0) or gcd(i32::MIN, i32::MIN).
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        // Only MIN % -1 overflows, and -1 divides everything.
+        (a, b) = (b, a.checked_rem(b).unwrap_or(T::ZERO));
+    }
+    checked_abs(a)
+}
+
+// lcm(0, n) is 0. None on overflow.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let g = gcd(a, b)?;
+    checked_abs((a / g).checked_mul(b)?)
+}
+
+// gcd of no numbers is 0, which every number is a multiple of.
+pub fn gcd_all<T: Integer>(nums: impl IntoIterator<Item = T>) -> Option<T> {
+    nums.into_iter().try_fold(T::ZERO, gcd)
+}
+
+// lcm of no numbers is 1.
+pub fn lcm_all<T: Integer>(nums: impl IntoIterator<Item = T>) -> Option<T> {
+    nums.into_iter().try_fold(T::ONE, lcm)
+}
+
+// Returns (g, x, y) with a * x + b * y == g == gcd(a, b).
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// Solves x = r (mod m) for every (r, m) pair. The moduli don't need to be
+// coprime. Returns (x, lcm of the moduli) with 0 <= x < lcm, or None if the
+// congruences contradict each other or the lcm doesn't fit in an i128.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let mut x = 0_i128;
+    let mut modulus = 1_i128;
+    for (r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let r = r.rem_euclid(m);
+
+        // Find t with x + modulus * t = r (mod m).
+        let (g, inv, _) = extended_gcd(modulus, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let m_over_g = m / g;
+        let t = ((diff / g) % m_over_g).checked_mul(inv % m_over_g)? % m_over_g;
+
+        let new_modulus = modulus.checked_mul(m_over_g)?;
+        x = x
+            .checked_add(modulus.checked_mul(t)?)?
+            .rem_euclid(new_modulus);
+        modulus = new_modulus;
+    }
+
+    Some((x, modulus))
+}
+
+// n choose k, with 0 when k > n. None on overflow or negative arguments.
+pub fn binomial<T: Integer>(n: T, k: T) -> Option<T> {
+    if n < T::ZERO || k < T::ZERO {
+        return None;
+    }
+    if k > n {
+        return Some(T::ZERO);
+    }
+    let k = k.min(n - k);
+
+    // Builds C(n - k + i, i) for i = 1..=k. Dividing out the gcd first keeps
+    // intermediate values no larger than the final answer.
+    let mut c = T::ONE;
+    let mut i = T::ONE;
+    while i <= k {
+        let m = n - k + i;
+        let g = gcd(c, i)?;
+        c = (c / g).checked_mul(m / (i / g))?;
+        i = i + T::ONE;
+    }
+
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12_u32, 18), Some(6));
+        assert_eq!(gcd(0_u32, 7), Some(7));
+        assert_eq!(gcd(-12_i64, 18), Some(6));
+        assert_eq!(gcd(i32::MIN, 0), None);
+        assert_eq!(gcd(i32::MIN, -1), Some(1));
+        assert_eq!(gcd(-1, i32::MIN), Some(1));
+        assert_eq!(gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(gcd(i64::MIN, 6), Some(2));
+        assert_eq!(lcm(4_u8, 6), Some(12));
+        assert_eq!(lcm(0_u8, 6), Some(0));
+        assert_eq!(lcm(-4_i32, 6), Some(12));
+        assert_eq!(lcm(200_u8, 3), None);
+        assert_eq!(lcm(u128::MAX, 1), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_gcd_and_lcm_all() {
+        assert_eq!(gcd_all([12_usize, 18, 27]), Some(3));
+        assert_eq!(gcd_all(Vec::<u64>::new()), Some(0));
+        assert_eq!(lcm_all(vec![2_u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+        assert_eq!(lcm_all((1..=50_u64).collect::<Vec<u64>>()), None);
+        assert_eq!(
+            lcm_all((1..=50_u128).collect::<Vec<u128>>()),
+            Some(3099044504245996706400)
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, 7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b).unwrap());
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(0, i128::MAX), (0, i128::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(5_u64, 2), Some(10));
+        assert_eq!(binomial(5_u64, 0), Some(1));
+        assert_eq!(binomial(5_u64, 5), Some(1));
+        assert_eq!(binomial(2_u64, 5), Some(0));
+        assert_eq!(binomial(-2_i32, 1), None);
+        assert_eq!(binomial(62_u64, 31), Some(465428353255261088));
+        assert_eq!(binomial(68_u64, 34), None);
+        assert_eq!(binomial(68_u128, 34), Some(28453041475240576740));
+        assert_eq!(binomial(30_u8, 1), Some(30));
+        assert_eq!(binomial(30_u8, 2), None);
+    }
+}
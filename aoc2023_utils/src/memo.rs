@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+// A cache for recursive solvers. The function computing a missing value gets
+// the memo back, so it can recurse through it:
+//
+//     fn count(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//         memo.get_or_insert_with(n, |memo| match n {
+//             0 | 1 => n,
+//             _ => count(memo, n - 1) + count(memo, n - 2),
+//         })
+//     }
+//
+// With a capacity limit, the oldest entries are evicted first.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    capacity: Option<usize>,
+    insertion_order: VecDeque<K>,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+            capacity: None,
+            insertion_order: VecDeque::new(),
+            stats: Stats::default(),
+        }
+    }
+
+    pub fn with_capacity_limit(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return;
+            }
+            while self.values.len() >= capacity {
+                let oldest = self.insertion_order.pop_front().unwrap();
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+            self.insertion_order.push_back(key.clone());
+        }
+        self.values.insert(key, value);
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Forgets the values but keeps counting.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.insertion_order.clear();
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_insert_with(n, |memo| match n {
+            0 | 1 => n,
+            _ => fib(memo, n - 1) + fib(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        // Each of 2..=90 misses once on the way down, then finds n - 2 cached.
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                evictions: 0
+            }
+        );
+
+        assert_eq!(fib(&mut memo, 50), 12586269025);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn test_capacity_limit() {
+        let mut memo = Memo::with_capacity_limit(3);
+        let mut calls = 0;
+        for key in [1, 2, 3, 1, 4, 1, 2] {
+            memo.get_or_insert_with(key, |_| {
+                calls += 1;
+                key * 10
+            });
+        }
+        // 1 is still around the second time, but 4 pushes it out before the
+        // third.
+        assert_eq!(calls, 6);
+        assert_eq!(memo.len(), 3);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 1,
+                misses: 6,
+                evictions: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_capacity_limit_with_recursion() {
+        let mut memo = Memo::with_capacity_limit(4);
+        assert_eq!(fib(&mut memo, 40), 102334155);
+        assert_eq!(memo.len(), 4);
+
+        let mut memo = Memo::with_capacity_limit(0);
+        assert_eq!(fib(&mut memo, 15), 610);
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().hits, 0);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut memo = Memo::new();
+        fib(&mut memo, 10);
+        memo.clear();
+        assert!(memo.is_empty());
+        fib(&mut memo, 10);
+        assert_eq!(memo.stats().misses, 22);
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+        let stats = Stats {
+            hits: 3,
+            misses: 1,
+            evictions: 0,
+        };
+        assert_eq!(stats.hit_rate(), 0.75);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::cancel;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+// $AOC_THREADS if it's set to a positive number, else one per core.
+pub fn num_threads() -> usize {
+    std::env::var(THREADS_VAR)
+        .ok()
+        .and_then(|threads| threads.parse::<usize>().ok())
+        .filter(|threads| *threads > 0)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+}
+
+// Calls `f` on every item, on a pool of worker threads with the `parallel`
+// feature and on this thread without it. Results come back in the same order
+// as `items` either way, so anything folded over them comes out the same.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    if cfg!(feature = "parallel") {
+        map_on_workers(items, f, num_threads())
+    } else {
+        items.iter().map(f).collect()
+    }
+}
+
+// Workers pull the next unclaimed index until there are none left, so a few
+// slow items don't hold up a whole pre-assigned chunk.
+fn map_on_workers<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+    num_workers: usize,
+) -> Vec<R> {
+    let num_workers = num_workers.min(items.len());
+    if num_workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    // Workers share the caller's cancellation token, and a cancelled worker
+    // unwinds back through the join below like any other panic.
+    let token = cancel::current();
+    let next_idx = AtomicUsize::new(0);
+    let mut results = thread::scope(|scope| {
+        let workers = (0..num_workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    let run = cancel::run_with(token.clone(), || loop {
+                        let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+                        if idx >= items.len() {
+                            break;
+                        }
+                        results.push((idx, f(&items[idx])));
+                    });
+                    if let Err(cancelled) = run {
+                        panic::resume_unwind(Box::new(cancelled));
+                    }
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect::<Vec<(usize, R)>>()
+    });
+
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let nums = (0..100).collect::<Vec<u64>>();
+        let squares = nums.iter().map(|n| n * n).collect::<Vec<u64>>();
+        assert_eq!(map(&nums, |n| n * n), squares);
+        assert!(map(&[] as &[u64], |n| n * n).is_empty());
+    }
+
+    #[test]
+    fn test_map_on_workers() {
+        let nums = (0..1000).collect::<Vec<u64>>();
+        let squares = nums.iter().map(|n| n * n).collect::<Vec<u64>>();
+        for num_workers in [0, 1, 3, 8, 2000] {
+            assert_eq!(map_on_workers(&nums, |n| n * n, num_workers), squares);
+        }
+        assert!(map_on_workers(&[] as &[u64], |n| n * n, 4).is_empty());
+    }
+
+    #[test]
+    fn test_map_on_workers_cancelled() {
+        let token = cancel::Token::new();
+        let result = cancel::run_with(Some(token.clone()), || {
+            map_on_workers(
+                &[1, 2, 3, 4],
+                |n| {
+                    if *n == 2 {
+                        token.cancel();
+                    }
+                    cancel::check();
+                    *n
+                },
+                2,
+            )
+        });
+        assert_eq!(result, Err(cancel::Cancelled));
+    }
+
+    #[test]
+    #[should_panic(expected = "bad item")]
+    fn test_map_on_workers_panic() {
+        map_on_workers(
+            &[1, 2, 3, 4],
+            |n| {
+                assert_ne!(*n, 3, "bad item");
+                *n
+            },
+            2,
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use crate::cancel;
+use crate::math::Integer;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// A graph to find the cheapest way through. Everything here is asked for
+// lazily, so states only ever exist once the search gets to them.
+pub trait Problem {
+    type State: Hash + Eq + Clone;
+    type Cost: Integer;
+
+    fn starts(&self) -> Vec<Self::State>;
+    fn successors(&self, state: &Self::State) -> Vec<Self::State>;
+    // The cost of moving from `from` to one of its successors, `to`.
+    fn cost(&self, from: &Self::State, to: &Self::State) -> Self::Cost;
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    // Never more than the cheapest way from `state` to a goal, or the path
+    // found might not be the cheapest. Zero, the default, makes the search
+    // Dijkstra's algorithm rather than A*.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::ZERO
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Path<S, C> {
+    pub cost: C,
+    // From a start to a goal, both included.
+    pub states: Vec<S>,
+}
+
+// A state the search is done with: nothing will get to it more cheaply.
+// `estimate` is `cost` plus the heuristic, and it never goes down from one
+// visit to the next as long as the heuristic never drops by more than the cost
+// of a move.
+#[derive(Debug, PartialEq)]
+pub struct Visit<'a, S, C> {
+    pub state: &'a S,
+    pub cost: C,
+    pub estimate: C,
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    prev: Option<usize>,
+}
+
+pub fn shortest_path<P: Problem>(problem: &P) -> Option<Path<P::State, P::Cost>> {
+    shortest_path_with(problem, |_| {})
+}
+
+// `on_visit` sees each state as the search finishes with it, in order.
+pub fn shortest_path_with<P: Problem>(
+    problem: &P,
+    mut on_visit: impl FnMut(&Visit<P::State, P::Cost>),
+) -> Option<Path<P::State, P::Cost>> {
+    // Nodes are referred to by index so the heap doesn't need to order states.
+    // It hands out the lowest estimate first and, out of those, the one that's
+    // got furthest, which is likeliest to be closest to a goal.
+    let mut nodes = Vec::<Node<P::State, P::Cost>>::new();
+    let mut node_idxs = HashMap::<P::State, usize>::new();
+    let mut frontier = BinaryHeap::new();
+
+    let mut push = |nodes: &mut Vec<Node<P::State, P::Cost>>,
+                    frontier: &mut BinaryHeap<_>,
+                    state: P::State,
+                    cost: P::Cost,
+                    prev: Option<usize>| {
+        let idx = match node_idxs.entry(state.clone()) {
+            Entry::Occupied(entry) if nodes[*entry.get()].cost <= cost => return,
+            Entry::Occupied(entry) => {
+                let idx = *entry.get();
+                nodes[idx].cost = cost;
+                nodes[idx].prev = prev;
+                idx
+            }
+            Entry::Vacant(entry) => {
+                nodes.push(Node { state, cost, prev });
+                *entry.insert(nodes.len() - 1)
+            }
+        };
+        let estimate = cost + problem.heuristic(&nodes[idx].state);
+        frontier.push((Reverse(estimate), cost, Reverse(idx)));
+    };
+
+    for start in problem.starts() {
+        push(&mut nodes, &mut frontier, start, P::Cost::ZERO, None);
+    }
+
+    while let Some((Reverse(estimate), cost, Reverse(idx))) = frontier.pop() {
+        cancel::check();
+        // Left behind when something cheaper got to the same state.
+        if cost > nodes[idx].cost {
+            continue;
+        }
+
+        let state = nodes[idx].state.clone();
+        on_visit(&Visit {
+            state: &state,
+            cost,
+            estimate,
+        });
+
+        if problem.is_goal(&state) {
+            return Some(Path {
+                cost,
+                states: trace_back(&nodes, idx),
+            });
+        }
+
+        for next in problem.successors(&state) {
+            let next_cost = cost + problem.cost(&state, &next);
+            push(&mut nodes, &mut frontier, next, next_cost, Some(idx));
+        }
+    }
+
+    None
+}
+
+fn trace_back<S: Clone, C>(nodes: &[Node<S, C>], idx: usize) -> Vec<S> {
+    let mut states = vec![];
+    let mut at = Some(idx);
+    while let Some(idx) = at {
+        states.push(nodes[idx].state.clone());
+        at = nodes[idx].prev;
+    }
+    states.reverse();
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coord, Grid, Rng};
+    use std::collections::VecDeque;
+
+    // Walks a maze from the top left to the bottom right, one step at a time.
+    struct Maze {
+        walls: Grid<bool>,
+        use_heuristic: bool,
+    }
+
+    impl Maze {
+        fn parse(s: &str) -> Self {
+            Self {
+                walls: Grid::parse(s, |c| c == '#'),
+                use_heuristic: false,
+            }
+        }
+
+        fn end(&self) -> Coord {
+            Coord::new(self.walls.num_rows() - 1, self.walls.num_cols() - 1)
+        }
+
+        fn bfs(&self) -> Option<u32> {
+            let mut dists = HashMap::from([(Coord::new(0, 0), 0)]);
+            let mut queue = VecDeque::from([Coord::new(0, 0)]);
+            while let Some(at) = queue.pop_front() {
+                for next in self.successors(&at) {
+                    if !dists.contains_key(&next) {
+                        dists.insert(next, dists[&at] + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            dists.get(&self.end()).copied()
+        }
+    }
+
+    impl Problem for Maze {
+        type State = Coord;
+        type Cost = u32;
+
+        fn starts(&self) -> Vec<Coord> {
+            vec![Coord::new(0, 0)]
+        }
+
+        fn successors(&self, state: &Coord) -> Vec<Coord> {
+            state
+                .neighbors4(self.walls.bounds())
+                .filter(|next| !self.walls[*next])
+                .collect()
+        }
+
+        fn cost(&self, _from: &Coord, _to: &Coord) -> u32 {
+            1
+        }
+
+        fn is_goal(&self, state: &Coord) -> bool {
+            *state == self.end()
+        }
+
+        fn heuristic(&self, state: &Coord) -> u32 {
+            if self.use_heuristic {
+                let end = self.end();
+                (end.row - state.row + end.col - state.col) as u32
+            } else {
+                0
+            }
+        }
+    }
+
+    // Cities along a road, with a pricier shortcut past the middle.
+    struct Road {
+        starts: Vec<u32>,
+        goal: u32,
+    }
+
+    impl Problem for Road {
+        type State = u32;
+        type Cost = u64;
+
+        fn starts(&self) -> Vec<u32> {
+            self.starts.clone()
+        }
+
+        fn successors(&self, state: &u32) -> Vec<u32> {
+            vec![state + 1, state + 3]
+        }
+
+        fn cost(&self, from: &u32, to: &u32) -> u64 {
+            match to - from {
+                1 => 2,
+                _ => 5,
+            }
+        }
+
+        fn is_goal(&self, state: &u32) -> bool {
+            *state == self.goal
+        }
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let maze = Maze::parse(concat!(
+            "..#....\n",
+            "#.#.##.\n",
+            "....#..\n",
+            ".##.#.#\n",
+            "...#...\n",
+        ));
+        let path = shortest_path(&maze).unwrap();
+        assert_eq!(path.cost, 16);
+        assert_eq!(path.states.len(), 17);
+        assert_eq!(path.states.first(), Some(&Coord::new(0, 0)));
+        assert_eq!(path.states.last(), Some(&maze.end()));
+        assert!(path
+            .states
+            .windows(2)
+            .all(|pair| maze.successors(&pair[0]).contains(&pair[1])));
+    }
+
+    #[test]
+    fn test_no_path() {
+        let maze = Maze::parse("..#\n.#.\n#..\n");
+        assert_eq!(shortest_path(&maze), None);
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let road = Road {
+            starts: vec![4],
+            goal: 4,
+        };
+        assert_eq!(
+            shortest_path(&road),
+            Some(Path {
+                cost: 0,
+                states: vec![4]
+            })
+        );
+    }
+
+    #[test]
+    fn test_costs() {
+        // Three steps of 1 cost 6, a jump of 3 costs 5.
+        let road = Road {
+            starts: vec![0],
+            goal: 6,
+        };
+        assert_eq!(
+            shortest_path(&road),
+            Some(Path {
+                cost: 10,
+                states: vec![0, 3, 6]
+            })
+        );
+
+        let road = Road {
+            starts: vec![0, 5],
+            goal: 7,
+        };
+        assert_eq!(
+            shortest_path(&road),
+            Some(Path {
+                cost: 4,
+                states: vec![5, 6, 7]
+            })
+        );
+    }
+
+    #[test]
+    fn test_heuristic_agrees_with_bfs() {
+        let mut rng = Rng::new(17);
+        for _ in 0..50 {
+            let input = (0..12)
+                .map(|row| {
+                    (0..15)
+                        .map(|col| {
+                            let is_end = (row, col) == (0, 0) || (row, col) == (11, 14);
+                            if !is_end && rng.chance(0.3) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .chain(['\n'])
+                        .collect::<String>()
+                })
+                .collect::<String>();
+            let mut maze = Maze::parse(&input);
+            let expected = maze.bfs();
+            assert_eq!(shortest_path(&maze).map(|path| path.cost), expected);
+            maze.use_heuristic = true;
+            assert_eq!(shortest_path(&maze).map(|path| path.cost), expected);
+        }
+    }
+
+    #[test]
+    fn test_visits() {
+        let mut maze = Maze::parse(&".....\n".repeat(5));
+        let mut num_visits = [0, 0];
+        for (use_heuristic, num_visits) in [false, true].into_iter().zip(&mut num_visits) {
+            maze.use_heuristic = use_heuristic;
+            let mut estimates = vec![];
+            shortest_path_with(&maze, |visit| {
+                assert_eq!(visit.estimate, visit.cost + maze.heuristic(visit.state));
+                estimates.push(visit.estimate);
+            });
+            assert!(estimates.windows(2).all(|pair| pair[0] <= pair[1]));
+            *num_visits = estimates.len();
+        }
+        // Dijkstra gets to everything else first, A* heads straight there.
+        assert_eq!(num_visits, [25, 9]);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::frames::Recorder;
+use crate::Image;
+use std::any::Any;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn label(&self) -> char {
+        match self {
+            Part::A => 'a',
+            Part::B => 'b',
+        }
+    }
+}
+
+pub trait Solution {
+    type Parsed: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    // Set to false while that part of a day hasn't been solved yet, and leave
+    // out its `partN()`, which is never called then.
+    const HAS_PART1: bool = true;
+    const HAS_PART2: bool = true;
+
+    // Knobs the puzzle turns between parts, like day11's expansion factor,
+    // that `solve_with_param()` can be asked to solve with other values.
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn parse(&self, input: &str) -> Self::Parsed;
+
+    fn part1(&self, _parsed: &Self::Parsed) -> Self::Answer1 {
+        unreachable!("part 1 isn't solved, HAS_PART1 is false")
+    }
+
+    fn part2(&self, _parsed: &Self::Parsed) -> Self::Answer2 {
+        unreachable!("part 2 isn't solved, HAS_PART2 is false")
+    }
+
+    // The idx'th thing in the parsed input for days where it's a list of
+    // things, None past the end.
+    fn show_item(&self, _parsed: &Self::Parsed, _idx: usize) -> Option<String> {
+        None
+    }
+
+    // Only called with one of PARAMS.
+    fn solve_with_param(&self, _parsed: &Self::Parsed, name: &str, _value: u32) -> String {
+        unreachable!("no param {name}")
+    }
+
+    // A picture of the solved puzzle, one pixel per tile, for days where
+    // there's something worth looking at.
+    fn render(&self, _parsed: &Self::Parsed) -> Option<Image> {
+        None
+    }
+
+    // Hands the recorder a frame for every step of the simulation, for days
+    // that are one. `start` is where to start from, in whatever form the day
+    // understands, or None for where the puzzle starts.
+    fn record(
+        &self,
+        _parsed: &Self::Parsed,
+        _start: Option<&str>,
+        _recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        Err("nothing to record".to_string())
+    }
+}
+
+// Object-safe view of a Solution so days with different parsed and answer
+// types can sit in one table.
+pub trait DynSolution {
+    fn parse_any(&self, input: &str) -> Box<dyn Any>;
+    fn has_part(&self, part: Part) -> bool;
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> String;
+    fn params(&self) -> &'static [&'static str];
+    fn show_item_any(&self, parsed: &dyn Any, idx: usize) -> Option<String>;
+    fn solve_with_param_any(&self, parsed: &dyn Any, name: &str, value: u32) -> String;
+    fn render_any(&self, parsed: &dyn Any) -> Option<Image>;
+    fn record_any(
+        &self,
+        parsed: &dyn Any,
+        start: Option<&str>,
+        recorder: &mut Recorder,
+    ) -> Result<(), String>;
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> &S::Parsed {
+    parsed
+        .downcast_ref::<S::Parsed>()
+        .expect("parsed input came from a different solution")
+}
+
+impl<S: Solution> DynSolution for S {
+    fn parse_any(&self, input: &str) -> Box<dyn Any> {
+        Box::new(self.parse(input))
+    }
+
+    fn has_part(&self, part: Part) -> bool {
+        match part {
+            Part::A => S::HAS_PART1,
+            Part::B => S::HAS_PART2,
+        }
+    }
+
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> String {
+        let parsed = downcast::<S>(parsed);
+        match part {
+            Part::A => self.part1(parsed).to_string(),
+            Part::B => self.part2(parsed).to_string(),
+        }
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        S::PARAMS
+    }
+
+    fn show_item_any(&self, parsed: &dyn Any, idx: usize) -> Option<String> {
+        self.show_item(downcast::<S>(parsed), idx)
+    }
+
+    fn solve_with_param_any(&self, parsed: &dyn Any, name: &str, value: u32) -> String {
+        self.solve_with_param(downcast::<S>(parsed), name, value)
+    }
+
+    fn render_any(&self, parsed: &dyn Any) -> Option<Image> {
+        self.render(downcast::<S>(parsed))
+    }
+
+    fn record_any(
+        &self,
+        parsed: &dyn Any,
+        start: Option<&str>,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        self.record(downcast::<S>(parsed), start, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SumAndMax;
+
+    impl Solution for SumAndMax {
+        type Parsed = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        const PARAMS: &'static [&'static str] = &["scale"];
+
+        fn parse(&self, input: &str) -> Self::Parsed {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+            parsed.iter().sum()
+        }
+
+        fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2 {
+            *parsed.iter().max().unwrap()
+        }
+
+        fn show_item(&self, parsed: &Self::Parsed, idx: usize) -> Option<String> {
+            parsed.get(idx).map(|n| n.to_string())
+        }
+
+        fn solve_with_param(&self, parsed: &Self::Parsed, _name: &str, value: u32) -> String {
+            (self.part1(parsed) * value).to_string()
+        }
+    }
+
+    struct PartOneOnly;
+
+    impl Solution for PartOneOnly {
+        type Parsed = String;
+        type Answer1 = String;
+        type Answer2 = String;
+
+        const HAS_PART2: bool = false;
+
+        fn parse(&self, input: &str) -> Self::Parsed {
+            input.to_string()
+        }
+
+        fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1 {
+            parsed.clone()
+        }
+    }
+
+    struct NotStarted;
+
+    impl Solution for NotStarted {
+        type Parsed = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        const HAS_PART1: bool = false;
+        const HAS_PART2: bool = false;
+
+        fn parse(&self, _input: &str) -> Self::Parsed {}
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let solution: &dyn DynSolution = &SumAndMax;
+        let parsed = solution.parse_any("3\n7\n5\n");
+        assert!(solution.has_part(Part::A));
+        assert!(solution.has_part(Part::B));
+        assert_eq!(solution.solve_any(parsed.as_ref(), Part::A), "15");
+        assert_eq!(solution.solve_any(parsed.as_ref(), Part::B), "7");
+        assert_eq!(solution.params(), &["scale"]);
+        assert_eq!(
+            solution.solve_with_param_any(parsed.as_ref(), "scale", 3),
+            "45"
+        );
+        assert_eq!(
+            solution.show_item_any(parsed.as_ref(), 1),
+            Some("7".to_string())
+        );
+        assert_eq!(solution.show_item_any(parsed.as_ref(), 3), None);
+
+        let solution: &dyn DynSolution = &PartOneOnly;
+        assert!(solution.has_part(Part::A));
+        assert!(!solution.has_part(Part::B));
+        assert!(solution.params().is_empty());
+        let parsed = solution.parse_any("abc");
+        assert_eq!(solution.show_item_any(parsed.as_ref(), 0), None);
+
+        let solution: &dyn DynSolution = &NotStarted;
+        assert!(!solution.has_part(Part::A));
+        assert!(!solution.has_part(Part::B));
+    }
+
+    #[test]
+    #[should_panic(expected = "HAS_PART2 is false")]
+    fn test_dyn_solution_missing_part() {
+        let parsed = PartOneOnly.parse_any("abc");
+        PartOneOnly.solve_any(parsed.as_ref(), Part::B);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dyn_solution_mismatched_parse() {
+        let parsed = PartOneOnly.parse_any("abc");
+        SumAndMax.solve_any(parsed.as_ref(), Part::A);
+    }
+}